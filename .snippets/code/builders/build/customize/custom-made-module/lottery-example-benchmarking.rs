@@ -0,0 +1,743 @@
+//! Benchmarking setup for the lottery example module
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Lottery;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	sp_io::hashing::blake2_256,
+	sp_runtime::traits::{Bounded, Saturating, Zero},
+	traits::{Currency, EnsureOrigin, Hooks},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use scale_info::prelude::{vec, vec::Vec};
+
+#[cfg(feature = "fungibles")]
+use frame_support::traits::fungibles;
+
+const SEED: u32 = 0;
+
+// Gives the account enough funds to buy tickets, and allows it to participate.
+// When paying in assets, the ticket and prize assets must already exist
+fn fund_account<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+	#[cfg(feature = "fungibles")]
+	<T::Assets as fungibles::Mutate<_>>::set_balance(
+		T::TicketAsset::get(), 
+		who, 
+		BalanceOf::<T>::max_value() / 2u32.into());
+	#[cfg(feature = "fungibles")]
+	if T::PrizeAsset::get() != T::TicketAsset::get() {
+		<T::Assets as fungibles::Mutate<_>>::set_balance(
+			T::PrizeAsset::get(), 
+			who, 
+			BalanceOf::<T>::max_value() / 2u32.into());
+	}
+	AllowList::<T>::insert(who, ());
+}
+
+// Turns the lotteries on, and creates a lottery accepting the maximum number 
+// of participants
+fn create_lottery<T: Config>() -> LotteryId {
+	Enabled::<T>::put(true);
+	let creator: T::AccountId = account("creator", 0, SEED);
+	fund_account::<T>(&creator);
+	let config = LotteryConfig {
+		ticket_cost: T::Currency::minimum_balance().max(1u32.into()).max(T::MinTicketCost::get()),
+		max_participants: T::MaxParticipants::get(),
+	};
+	let id = Lottery::<T>::get_next_lottery_id();
+	Lottery::<T>::create_lottery(RawOrigin::Signed(creator).into(), config, RandomnessSource::OnChain)
+		.expect("the lottery settings are valid; qed");
+	id
+}
+
+// Registers the given number of participants, with one ticket each
+fn add_participants<T: Config>(lottery_id: LotteryId, count: u32) {
+	for index in 0..count {
+		let participant: T::AccountId = account("participant", index, SEED);
+		fund_account::<T>(&participant);
+		Lottery::<T>::buy_ticket(RawOrigin::Signed(participant).into(), lottery_id, 1)
+			.expect("participant is funded and the lottery is not full; qed");
+	}
+}
+
+// Moves to the block in which the ticket sales end, if any
+fn close_sales<T: Config>(lottery_id: LotteryId) {
+	if let Some(end) = Lottery::<T>::get_round_end(lottery_id) {
+		frame_system::Pallet::<T>::set_block_number(end);
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_lottery() {
+		Enabled::<T>::put(true);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let config = LotteryConfig {
+			ticket_cost: T::Currency::minimum_balance().max(1u32.into()).max(T::MinTicketCost::get()),
+			max_participants: T::MaxParticipants::get(),
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), config, RandomnessSource::CommitReveal);
+
+		assert_eq!(Lottery::<T>::get_next_lottery_id(), 1);
+	}
+
+	// The participants list is read and written on every purchase,
+	// so the cost scales with the number of participants. Benchmarking
+	// with the `indexed-participants` feature measures the indexed 
+	// participants instead, whose cost doesn't depend on them
+	#[benchmark]
+	fn buy_ticket(p: Linear<0, { T::MaxParticipants::get() - 1 }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, 1);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p + 1);
+	}
+
+	// The referrer participates in the lottery, so the bonus ticket is granted 
+	// on top of the work of a purchase
+	#[benchmark]
+	fn buy_ticket_with_referral(p: Linear<1, { T::MaxParticipants::get() - 1 }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let referrer: T::AccountId = account("participant", p - 1, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), lottery_id, 1, referrer.clone());
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p + 1);
+		assert!(Lottery::<T>::get_referral((referrer, caller)).is_some());
+	}
+
+	// The memo is stored on top of the work of a purchase
+	#[benchmark]
+	fn buy_ticket_with_data(p: Linear<0, { T::MaxParticipants::get() - 1 }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let data = BoundedVec::truncate_from(vec![7u8; T::MaxMemo::get() as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), lottery_id, 1, data);
+
+		assert!(Lottery::<T>::get_ticket_memo(lottery_id, caller).is_some());
+	}
+
+	#[benchmark]
+	fn subscribe() {
+		let lottery_id = create_lottery::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), lottery_id, 1);
+
+		assert!(Lottery::<T>::get_subscription(lottery_id, caller).is_some());
+	}
+
+	#[benchmark]
+	fn unsubscribe() -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		Lottery::<T>::subscribe(RawOrigin::Signed(caller.clone()).into(), lottery_id, 1)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), lottery_id);
+
+		assert!(Lottery::<T>::get_subscription(lottery_id, caller).is_none());
+		Ok(())
+	}
+
+	// The payer gives the funds to the beneficiary, on top of the work
+	// of a purchase
+	#[benchmark]
+	fn buy_ticket_for(p: Linear<0, { T::MaxParticipants::get() - 1 }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		fund_account::<T>(&beneficiary);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, beneficiary, 1);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p + 1);
+	}
+
+	// Every beneficiary is registered, so the cost scales with the
+	// size of the batch
+	#[benchmark]
+	fn buy_tickets(n: Linear<1, { T::MaxBatch::get().min(T::MaxParticipants::get()) }>) {
+		let lottery_id = create_lottery::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let beneficiaries: Vec<T::AccountId> = (0..n)
+			.map(|index| {
+				let beneficiary: T::AccountId = account("beneficiary", index, SEED);
+				fund_account::<T>(&beneficiary);
+				beneficiary
+			})
+			.collect();
+		let beneficiaries: BoundedVec<_, T::MaxBatch> = beneficiaries
+			.try_into()
+			.expect("the batch fits the bound; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, beneficiaries);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), n);
+	}
+
+	#[benchmark]
+	fn pause() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert!(Lottery::<T>::get_paused());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unpause() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Paused::<T>::put(true);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert!(!Lottery::<T>::get_paused());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn enable_lottery() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert!(Lottery::<T>::get_enabled());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn disable_lottery() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Enabled::<T>::put(true);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert!(!Lottery::<T>::get_enabled());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_add_participant(p: Linear<0, { T::MaxParticipants::get() - 1 }>) -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, who);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p + 1);
+		Ok(())
+	}
+
+	// The removed participant's funds are collected into the pot, so the cost
+	// includes a transfer on top of the participants list update
+	#[benchmark]
+	fn force_remove_participant(p: Linear<1, { T::MaxParticipants::get() }>) -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("participant", p - 1, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, who);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p - 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reset_nonce() {
+		Enabled::<T>::put(true);
+		Nonce::<T>::put(u64::MAX);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root);
+
+		assert_eq!(Lottery::<T>::get_nonce(), 0);
+	}
+
+	#[benchmark]
+	fn emergency_withdraw() {
+		let lottery_id = create_lottery::<T>();
+		let pot = Lottery::<T>::get_pallet_account(lottery_id);
+		fund_account::<T>(&pot);
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let stray = Lottery::<T>::current_pot(lottery_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, lottery_id, dest, stray);
+
+		assert!(Lottery::<T>::current_pot(lottery_id).is_zero());
+	}
+
+	#[benchmark]
+	fn leave_lottery(p: Linear<1, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		Lottery::<T>::buy_ticket(RawOrigin::Signed(caller.clone()).into(), lottery_id, 1)
+			.expect("caller is funded and the lottery is not full; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p - 1);
+	}
+
+	// Each ticket given back voids its number, so the cost also scales with 
+	// the number of tickets
+	#[benchmark]
+	fn reduce_tickets(
+		p: Linear<1, { T::MaxParticipants::get() }>,
+		t: Linear<1, { T::MaxTicketsPerAccount::get() }>,
+	) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		Lottery::<T>::buy_ticket(RawOrigin::Signed(caller.clone()).into(), lottery_id, t)
+			.expect("caller is funded and the lottery is not full; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, t);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), p - 1);
+	}
+
+	// Selecting the winners walks the participants list, so the cost
+	// scales with the number of participants
+	#[benchmark]
+	fn award_prize(p: Linear<{ T::MinParticipants::get().max(1) }, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		close_sales::<T>(lottery_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+	}
+
+	// The round is drawn as on `award_prize`, once it's stale
+	#[benchmark]
+	fn force_settle_stale_round(
+		p: Linear<{ T::MinParticipants::get().max(1) }, { T::MaxParticipants::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let threshold = T::StaleRoundBlocks::get();
+		if threshold.is_zero() {
+			return Err(BenchmarkError::Weightless);
+		}
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		let since = Lottery::<T>::get_round_end(lottery_id)
+			.unwrap_or_else(frame_system::Pallet::<T>::block_number);
+		frame_system::Pallet::<T>::set_block_number(since + threshold + 1u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+		Ok(())
+	}
+
+	// The winners are found by looking up the owners of the winning numbers, but 
+	// the funds held for every participant are collected, so the cost still 
+	// scales with the number of participants
+	#[benchmark]
+	fn draw(p: Linear<{ T::MinParticipants::get().max(1) }, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		close_sales::<T>(lottery_id);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+	}
+
+	#[benchmark]
+	fn draw_unsigned(p: Linear<{ T::MinParticipants::get().max(1) }, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		close_sales::<T>(lottery_id);
+		let round = Lottery::<T>::get_current_round(lottery_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, lottery_id, round);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+	}
+
+	#[benchmark]
+	fn set_ticket_cost() -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let new_cost = T::Currency::minimum_balance().saturating_add(2u32.into()).max(T::MinTicketCost::get());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, new_cost);
+
+		assert_eq!(Lottery::<T>::get_lottery(lottery_id).map(|lottery| lottery.ticket_cost), Some(new_cost));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_prize_source() -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let source = PrizeSource::Fixed { amount: T::Currency::minimum_balance().saturating_add(1u32.into()) };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, source);
+
+		assert_eq!(Lottery::<T>::get_prize_source(lottery_id), source);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_max_participants() -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let new_cap = T::MaxParticipants::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, new_cap);
+
+		assert_eq!(Lottery::<T>::get_lottery(lottery_id).map(|lottery| lottery.max_participants), Some(new_cap));
+		Ok(())
+	}
+
+	// Every participant is refunded, so the cost scales with the
+	// number of participants
+	#[benchmark]
+	fn cancel_lottery(p: Linear<1, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+	}
+
+	// The last page refunds its participants and resets the round, which is the
+	// most expensive page
+	#[benchmark]
+	fn cancel_lottery_paged(p: Linear<1, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, lottery_id, p);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+		assert!(Lottery::<T>::get_cancellation_progress(lottery_id).is_none());
+	}
+
+	#[benchmark]
+	fn add_to_allowlist() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("allowed", 0, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Lottery::<T>::get_allow_list(&who).is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_from_allowlist() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("allowed", 0, SEED);
+		AllowList::<T>::insert(&who, ());
+		AllowListCount::<T>::put(1);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Lottery::<T>::get_allow_list(&who).is_none());
+		Ok(())
+	}
+
+	// Every account of the batch is looked up and added, so the cost scales
+	// with the size of the batch
+	#[benchmark]
+	fn add_to_allowlist_batch(n: Linear<1, { T::MaxBatch::get() }>) -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: Vec<T::AccountId> = (0..n).map(|index| account("allowed", index, SEED)).collect();
+		let who: BoundedVec<_, T::MaxBatch> = who.try_into().expect("the batch fits the bound; qed");
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who);
+
+		assert_eq!(Lottery::<T>::get_allowlist_count(), n);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_from_allowlist_batch(n: Linear<1, { T::MaxBatch::get() }>) -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: Vec<T::AccountId> = (0..n).map(|index| account("allowed", index, SEED)).collect();
+		for account in who.iter() {
+			AllowList::<T>::insert(account, ());
+		}
+		AllowListCount::<T>::put(n);
+		let who: BoundedVec<_, T::MaxBatch> = who.try_into().expect("the batch fits the bound; qed");
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who);
+
+		assert_eq!(Lottery::<T>::get_allowlist_count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_fee_exempt() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("exempt", 0, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Lottery::<T>::get_fee_exempt(&who).is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_fee_exempt() -> Result<(), BenchmarkError> {
+		Enabled::<T>::put(true);
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("exempt", 0, SEED);
+		FeeExempt::<T>::insert(&who, ());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Lottery::<T>::get_fee_exempt(&who).is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn commit_seed() -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		let origin = T::SeedOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let hash = blake2_256(&[0u8; 32]);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, hash);
+
+		assert_eq!(Lottery::<T>::get_seed_commitment(lottery_id), Some(hash));
+		Ok(())
+	}
+
+	// The winners are drawn after checking the seed, so the cost
+	// scales with the number of participants
+	#[benchmark]
+	fn reveal_and_draw(
+		p: Linear<{ T::MinParticipants::get().max(1) }, { T::MaxParticipants::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, p);
+		close_sales::<T>(lottery_id);
+		let origin = T::SeedOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let seed = [7u8; 32];
+		SeedCommitments::<T>::insert(lottery_id, blake2_256(&seed));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lottery_id, seed);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn donate() {
+		let lottery_id = create_lottery::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let amount = T::Currency::minimum_balance().max(1u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, amount);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+	}
+
+	#[benchmark]
+	fn scratch() {
+		let lottery_id = create_lottery::<T>();
+		let donor: T::AccountId = account("donor", 0, SEED);
+		fund_account::<T>(&donor);
+		let prize = T::ScratchPrize::get().saturating_add(T::Currency::minimum_balance());
+		Lottery::<T>::donate(RawOrigin::Signed(donor).into(), lottery_id, prize.max(1u32.into()))
+			.expect("donor is funded; qed");
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		let settles_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RandomnessDelay::get())
+			.saturating_add(1u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id);
+
+		assert_eq!(Lottery::<T>::participant_count(lottery_id), 0);
+		assert_eq!(Lottery::<T>::get_pending_scratches(settles_at).len(), 1);
+	}
+
+	// The scratches are rolled in the hook of the block they are due on, so the
+	// cost scales with the number of scratches rolled on the block
+	#[benchmark]
+	fn settle_scratches(n: Linear<0, { T::MaxScratchesPerBlock::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		let donor: T::AccountId = account("donor", 0, SEED);
+		fund_account::<T>(&donor);
+		let prize = T::ScratchPrize::get()
+			.saturating_mul(n.max(1).into())
+			.saturating_add(T::Currency::minimum_balance());
+		Lottery::<T>::donate(RawOrigin::Signed(donor).into(), lottery_id, prize.max(1u32.into()))
+			.expect("donor is funded; qed");
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		for _ in 0..n {
+			Lottery::<T>::scratch(RawOrigin::Signed(caller.clone()).into(), lottery_id)
+				.expect("the pot can pay the scratch prize; qed");
+		}
+		let settles_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RandomnessDelay::get())
+			.saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(settles_at);
+
+		#[block]
+		{
+			Lottery::<T>::on_initialize(settles_at);
+		}
+
+		assert!(Lottery::<T>::get_pending_scratches(settles_at).is_empty());
+	}
+
+	#[benchmark]
+	fn claim_prize() {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, T::MinParticipants::get().max(1));
+		close_sales::<T>(lottery_id);
+		Lottery::<T>::award_prize(RawOrigin::Root.into(), lottery_id)
+			.expect("the round has enough participants; qed");
+		let winner = Lottery::<T>::get_winner_history(lottery_id)
+			.last()
+			.map(|record| record.winner.clone())
+			.expect("the prize was just awarded; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(winner.clone()), lottery_id);
+
+		assert!(Lottery::<T>::get_unclaimed_prize(lottery_id, &winner).is_none());
+	}
+
+	// A single payout out of the ones done on idle blocks
+	#[benchmark]
+	fn on_idle_payout() {
+		let lottery_id = create_lottery::<T>();
+		add_participants::<T>(lottery_id, T::MinParticipants::get().max(1));
+		close_sales::<T>(lottery_id);
+		Lottery::<T>::award_prize(RawOrigin::Root.into(), lottery_id)
+			.expect("the round has enough participants; qed");
+		let round = Lottery::<T>::get_current_round(lottery_id) - 1;
+		let winners = Lottery::<T>::get_pending_payouts(lottery_id, round)
+			.expect("the prize was just awarded; qed");
+		PendingPayouts::<T>::insert(lottery_id, round, BoundedVec::truncate_from(winners[..1].to_vec()));
+		let weight = T::WeightInfo::on_idle_payout().saturating_add(T::DbWeight::get().reads(1));
+
+		#[block]
+		{
+			Lottery::<T>::on_idle(frame_system::Pallet::<T>::block_number(), weight);
+		}
+
+		assert!(Lottery::<T>::get_pending_payouts(lottery_id, round).is_none());
+	}
+
+	// Every prize looked at is expired, so the cost scales with
+	// the number of prizes swept
+	#[benchmark]
+	fn sweep_expired(n: Linear<0, { T::MaxParticipants::get() }>) {
+		let lottery_id = create_lottery::<T>();
+		for index in 0..n {
+			let winner: T::AccountId = account("winner", index, SEED);
+			UnclaimedPrizes::<T>::insert(lottery_id, &winner, (BalanceOf::<T>::from(1u32), Zero::zero()));
+			TotalUnclaimed::<T>::mutate(lottery_id, |total| *total = total.saturating_add(1u32.into()));
+		}
+		frame_system::Pallet::<T>::set_block_number(T::ClaimDeadline::get().max(1u32.into()));
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), lottery_id, n);
+
+		assert!(Lottery::<T>::get_total_unclaimed(lottery_id).is_zero());
+	}
+}
@@ -1,99 +1,1135 @@
 #[pallet::call]
 impl<T: Config> Pallet<T> {
 
-    #[pallet::call_index(0)]
-    #[pallet::weight(0)]
-    pub fn buy_ticket(origin: OriginFor<T>) -> DispatchResult {
+    #[pallet::call_index(6)]
+    #[pallet::weight(T::WeightInfo::create_lottery())]
+    pub fn create_lottery(
+        origin: OriginFor<T>,
+        config: LotteryConfig<BalanceOf<T>>,
+        randomness: RandomnessSource,
+    ) -> DispatchResult {
 
         // 1. Validates the origin signature
-        let buyer = ensure_signed(origin)?;
+        let creator = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
 
-        // 2. Checks that the user has enough balance to afford the ticket price
+        // 2. Checks the lottery settings, and that there is room for a new lottery
+        ensure!(!config.ticket_cost.is_zero(), Error::<T>::InvalidTicketCost);
+        ensure!(config.ticket_cost >= T::MinTicketCost::get(), Error::<T>::TicketCostTooLow);
         ensure!(
-            T::Currency::free_balance(&buyer) >= T::TicketCost::get(),
-            Error::<T>::NotEnoughCurrency
+            config.max_participants > 0 && 
+                config.max_participants <= T::MaxParticipants::get(),
+            Error::<T>::InvalidMaxParticipants
         );
+        let id = Self::get_next_lottery_id();
+        ensure!(id < T::MaxLotteries::get(), Error::<T>::TooManyLotteries);
+
+        // 3. Reserves the creation deposit on the creator's account, so lotteries
+        // can't be created for free
+        let deposit = T::LotteryCreationDeposit::get();
+        if !deposit.is_zero() {
+            T::Currency::reserve_named(&Self::reserve_id(), &creator, deposit)?;
+            CreationDeposits::<T>::insert(id, (&creator, deposit));
+        }
+
+        // 4. Stores the new lottery, ready to sell tickets, along with the source
+        // of the randomness its rounds are drawn with
+        Lotteries::<T>::insert(id, config);
+        LotteryRandomness::<T>::insert(id, randomness);
+        NextLotteryId::<T>::put(id.saturating_add(1));
+
+        // 5. Notify the events
+        Self::deposit_event(Event::LotteryCreated { id });
+        if !deposit.is_zero() {
+            Self::deposit_event(Event::CreationDepositReserved { lottery_id: id, creator, amount: deposit });
+        }
+        Ok(())
+    }
+
+    #[pallet::call_index(0)]
+    #[pallet::weight(T::WeightInfo::buy_ticket(T::MaxParticipants::get()))]
+    pub fn buy_ticket(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        quantity: u32,
+    ) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature, and that the user is not buying too soon
+        let buyer = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        Self::throttle_buy(&buyer)?;
+
+        // 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+        // ignored by the policy is free
+        let bought = Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)?;
+        Ok(Self::purchase_post_info(bought))
+    }
+
+    #[pallet::call_index(14)]
+    #[pallet::weight(T::WeightInfo::buy_ticket_for(T::MaxParticipants::get()))]
+    pub fn buy_ticket_for(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        beneficiary: T::AccountId,
+        quantity: u32,
+    ) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature, and that the signer is not buying too soon
+        let payer = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        Self::throttle_buy(&payer)?;
+
+        // 2. Buys the tickets for the beneficiary, who is registered as the 
+        // participant and can win the prize, while the signer pays for them. A 
+        // duplicate purchase ignored by the policy is free
+        let bought = Self::do_buy_ticket(&payer, beneficiary, lottery_id, quantity)?;
+        Ok(Self::purchase_post_info(bought))
+    }
 
-        // 3. Checks that the user is not already participating
-        if let Some(participants) = Self::get_participants() {
-            ensure!(
-                !participants.contains(&buyer),
-                Error::<T>::AccountAlreadyParticipating
-            );
-        }
-
-        // 4. Adds the user as a new participant for the prize
-        match Self::get_participants() {
-            Some(mut participants) => { 
-                ensure!(
-                    participants.try_push(buyer.clone()).is_ok(), 
-                    Error::<T>::CanNotAddParticipant
-                );
-                Participants::<T>::set(Some(participants));
-            }, 
-            None => {
-                let mut participants = BoundedVec::new();
-                ensure!(
-                    participants.try_push(buyer.clone()).is_ok(), 
-                    Error::<T>::CanNotAddParticipant
-                );
-                Participants::<T>::set(Some(participants));
+    #[pallet::call_index(15)]
+    #[pallet::weight(T::WeightInfo::buy_tickets(beneficiaries.len() as u32))]
+    pub fn buy_tickets(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        beneficiaries: BoundedVec<T::AccountId, T::MaxBatch>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin signature, and that the signer is not buying too 
+        // soon. The whole batch counts as a single purchase
+        let payer = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        Self::throttle_buy(&payer)?;
+
+        // 2. Buys a ticket for each beneficiary, within a storage transaction so
+        // the signer isn't charged for a partial batch if any purchase fails. 
+        // Only the purchases made count, not the ones ignored by the policy
+        let count = with_transaction(|| {
+            let mut count = 0u32;
+            for beneficiary in beneficiaries {
+                match Self::do_buy_ticket(&payer, beneficiary, lottery_id, 1) {
+                    Ok(true) => count.saturating_inc(),
+                    Ok(false) => {},
+                    Err(error) => return TransactionOutcome::Rollback(Err(error)),
+                }
             }
+            TransactionOutcome::Commit(Ok(count))
+        })?;
+
+        // 3. Notify the event
+        Self::deposit_event(Event::TicketsBought { lottery_id, payer, count });
+        Ok(())
+    }
+
+    #[pallet::call_index(20)]
+    #[pallet::weight(T::WeightInfo::buy_ticket_with_referral(T::MaxParticipants::get()))]
+    pub fn buy_ticket_with_referral(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        quantity: u32,
+        referrer: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature, that the user is not referring itself,
+        // and that it's not buying too soon
+        let buyer = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(referrer != buyer, Error::<T>::SelfReferral);
+        Self::throttle_buy(&buyer)?;
+
+        // 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+        // ignored by the policy is free, and grants no bonus ticket
+        if !Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)? {
+            return Ok(Pays::No.into());
+        }
+
+        // 3. Grants a bonus ticket to the referrer, only once for each account
+        // referred, and only if the referrer participates in the current round
+        // with room left for another ticket. Otherwise the purchase goes on alone.
+        // The bonus ticket counts as sold, as it's refunded like the others
+        if Referrals::<T>::contains_key((&referrer, &buyer)) {
+            return Ok(().into());
+        }
+        let Some(tickets) = Self::tickets_held(lottery_id, &referrer)
+            .filter(|tickets| *tickets < T::MaxTicketsPerAccount::get()) else {
+            return Ok(().into());
         };
+        Self::set_tickets(lottery_id, &referrer, tickets.saturating_add(1))?;
+        Self::issue_tickets(lottery_id, &referrer, 1)?;
+        TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_add(1));
+        Referrals::<T>::insert((&referrer, &buyer), ());
+
+        // 4. Notify the event
+        Self::deposit_event(Event::ReferralRewarded { lottery_id, referrer, referred: buyer });
+        Ok(().into())
+    }
 
-        // 5. Transfers the ticket cost to the module's account
-        // to be hold until transferred to the winner
-        T::Currency::transfer(
-            &buyer, 
-            &Self::get_pallet_account(), 
-            T::TicketCost::get(), 
-            ExistenceRequirement::KeepAlive)?;
-        
-        // 6. Notify the event
-        Self::deposit_event(Event::TicketBought { who: buyer });
+    // Buys tickets as `buy_ticket` does, attaching a memo to the user's tickets,
+    // which replaces any memo attached before in the round
+    #[pallet::call_index(30)]
+    #[pallet::weight(T::WeightInfo::buy_ticket_with_data(T::MaxParticipants::get()))]
+    pub fn buy_ticket_with_data(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        quantity: u32,
+        data: BoundedVec<u8, T::MaxMemo>,
+    ) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature, and that the user is not buying too soon
+        let buyer = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        Self::throttle_buy(&buyer)?;
+
+        // 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+        // ignored by the policy is free, and keeps the memo attached before
+        if !Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)? {
+            return Ok(Pays::No.into());
+        }
+
+        // 3. Attaches the memo to the user's tickets
+        TicketMemos::<T>::insert(lottery_id, &buyer, data);
+        Ok(().into())
+    }
+
+    // Prepays the user's entry into the given number of coming rounds, with a
+    // ticket each. The funds for all the rounds are held up front, priced as 
+    // the first ticket of a round, and each entry pays the price of its ticket
+    // out of them. The subscription ends, refunding the rest, once the funds 
+    // left don't cover an entry
+    #[pallet::call_index(34)]
+    #[pallet::weight(T::WeightInfo::subscribe())]
+    pub fn subscribe(origin: OriginFor<T>, lottery_id: LotteryId, rounds: u32) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let who = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        let lottery = Self::get_lottery(lottery_id).ok_or(Error::<T>::LotteryNotFound)?;
+
+        // 2. Checks that rounds are being subscribed to, by an account that is
+        // not subscribed yet
+        ensure!(rounds > 0, Error::<T>::InvalidAmount);
+        ensure!(!Subscriptions::<T>::contains_key(lottery_id, &who), Error::<T>::AlreadySubscribed);
+
+        // 3. Holds the funds for every round
+        let funds = Self::tickets_cost(&lottery, 0, 1)?
+            .checked_mul(&rounds.into())
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        ensure!(Self::can_afford(&who, funds), Error::<T>::NotEnoughCurrency);
+        Self::hold_ticket_funds(lottery_id, &who, &who, funds)?;
+        Subscriptions::<T>::insert(lottery_id, &who, Subscription { remaining_rounds: rounds, funds });
+
+        // 4. Notify the event
+        Self::deposit_event(Event::Subscribed { lottery_id, who, rounds, funds });
+        Ok(())
+    }
+
+    // Ends the user's subscription, refunding the funds of the rounds it was not
+    // entered into yet. The tickets of the entries already made are kept
+    #[pallet::call_index(35)]
+    #[pallet::weight(T::WeightInfo::unsubscribe())]
+    pub fn unsubscribe(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let who = ensure_signed(origin)?;
+
+        // 2. Ends the subscription, and refunds its funds
+        let subscription = Subscriptions::<T>::get(lottery_id, &who).ok_or(Error::<T>::NotSubscribed)?;
+        Self::end_subscription(lottery_id, &who, subscription.funds)
+    }
+
+    #[pallet::call_index(4)]
+    #[pallet::weight(T::WeightInfo::leave_lottery(T::MaxParticipants::get()))]
+    pub fn leave_lottery(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature, and that the sales are open
+        let who = ensure_signed(origin)?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+        ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+        Self::ensure_sales_open(lottery_id)?;
+
+        // 2. Removes one of the user's tickets, and the user from the participants 
+        // list if it was the last one. A user holding several tickets can leave
+        // partially, or leave completely by calling once per ticket
+        let held_tickets = Self::tickets_held(lottery_id, &who).ok_or(Error::<T>::NotParticipating)?;
+        let remaining_tickets = held_tickets.saturating_sub(1);
+        Self::set_tickets(lottery_id, &who, remaining_tickets)?;
+        if remaining_tickets == 0 {
+            TicketMemos::<T>::remove(lottery_id, &who);
+            #[cfg(feature = "nfts")]
+            TicketItems::<T>::remove(lottery_id, &who);
+        }
+        Self::void_ticket(lottery_id, &who);
+        if Self::participant_count(lottery_id) == 0 {
+            Self::clear_round_end(lottery_id);
+            Self::clear_tickets(lottery_id);
+        }
+
+        // 3. Refunds the ticket by releasing its share of the funds held
+        // for the user's tickets, which may have been bought at different costs
+        let reserved = Self::get_reserved_funds(lottery_id, &who);
+        let refund = if remaining_tickets == 0 {
+            ReservedFunds::<T>::remove(lottery_id, &who);
+            reserved
+        } else {
+            let refund = reserved / held_tickets.into();
+            ReservedFunds::<T>::insert(lottery_id, &who, reserved.saturating_sub(refund));
+            refund
+        };
+        Self::release_ticket_funds(lottery_id, &who, refund)?;
+        TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_sub(1));
+
+        // 4. Notify the event
+        Self::deposit_event(Event::TicketRefunded { lottery_id, who });
+        Ok(())
+    }
+
+    #[pallet::call_index(23)]
+    #[pallet::weight(T::WeightInfo::reduce_tickets(T::MaxParticipants::get(), *amount))]
+    pub fn reduce_tickets(origin: OriginFor<T>, lottery_id: LotteryId, amount: u32) -> DispatchResult {
+
+        // 1. Validates the origin signature, and that the sales are open
+        let who = ensure_signed(origin)?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+        ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+        ensure!(amount > 0, Error::<T>::InvalidQuantity);
+        Self::ensure_sales_open(lottery_id)?;
+
+        // 2. Removes the amount of tickets from the user's tickets, and the user 
+        // from the participants list if none are left
+        let held_tickets = Self::tickets_held(lottery_id, &who).ok_or(Error::<T>::NotParticipating)?;
+        ensure!(amount <= held_tickets, Error::<T>::InsufficientTickets);
+        let remaining_tickets = held_tickets - amount;
+        Self::set_tickets(lottery_id, &who, remaining_tickets)?;
+        if remaining_tickets == 0 {
+            TicketMemos::<T>::remove(lottery_id, &who);
+            #[cfg(feature = "nfts")]
+            TicketItems::<T>::remove(lottery_id, &who);
+        }
+        for _ in 0..amount {
+            Self::void_ticket(lottery_id, &who);
+        }
+        if Self::participant_count(lottery_id) == 0 {
+            Self::clear_round_end(lottery_id);
+            Self::clear_tickets(lottery_id);
+        }
+
+        // 3. Refunds the tickets by releasing their share of the funds held
+        // for the user's tickets, which may have been bought at different costs
+        let reserved = Self::get_reserved_funds(lottery_id, &who);
+        let refund = if remaining_tickets == 0 {
+            ReservedFunds::<T>::remove(lottery_id, &who);
+            reserved
+        } else {
+            let refund = (reserved / held_tickets.into()).saturating_mul(amount.into());
+            ReservedFunds::<T>::insert(lottery_id, &who, reserved.saturating_sub(refund));
+            refund
+        };
+        Self::release_ticket_funds(lottery_id, &who, refund)?;
+        TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_sub(amount.into()));
+
+        // 4. Notify the event
+        Self::deposit_event(Event::TicketsReduced { lottery_id, who, amount });
         Ok(())
     }
 
+    #[pallet::call_index(5)]
+    #[pallet::weight(T::WeightInfo::draw(T::MaxParticipants::get()))]
+    pub fn draw(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature, any account can trigger the draw
+        let who = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Checks that the round can be drawn
+        Self::ensure_can_draw(lottery_id)?;
+
+        // 3. Refunds the round if it closed without enough participants, or draws 
+        // the winner and awards the prize
+        if Self::refund_if_undersubscribed(lottery_id) {
+            return Ok(());
+        }
+        Self::do_award_prize(lottery_id, DrawSeed::OnChain, Some(&who))
+    }
+
+    #[pallet::call_index(19)]
+    #[pallet::weight(T::WeightInfo::draw_unsigned(T::MaxParticipants::get()))]
+    pub fn draw_unsigned(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        round: u32,
+    ) -> DispatchResult {
+
+        // 1. Validates that the transaction is unsigned, as submitted by the 
+        // offchain worker
+        ensure_none(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Checks that the draw is for the current round, and that it can be drawn
+        ensure!(round == Self::get_current_round(lottery_id), Error::<T>::RoundAlreadyDrawn);
+        Self::ensure_can_draw(lottery_id)?;
+
+        // 3. Draws the winner and awards the prize
+        Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)
+    }
+
     #[pallet::call_index(1)]
-    #[pallet::weight(0)]
-    pub fn award_prize(origin: OriginFor<T>) -> DispatchResult {
+    #[pallet::weight(T::WeightInfo::award_prize(T::MaxParticipants::get()))]
+    pub fn award_prize(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResultWithPostInfo {
 
         // 1. Validates the origin signature
         let _who = ensure_root(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
 
-        match Self::get_participants() {
-            Some(participants) => { 
-                
-                // 2. Gets a random number from the randomness module
-                let nonce = Self::get_and_increment_nonce();
-                let (random_seed, _) = T::MyRandomness::random(&nonce);
-                let random_number = <u32>::decode(&mut random_seed.as_ref())
-                    .expect("secure hashes should always be bigger than u32; qed");
-                
-                // 3. Selects the winner from the participants lit
-                let winner_index = random_number as usize % participants.len();
-                let winner = participants.as_slice().get(winner_index).unwrap();
-
-                // 4. Transfers the total prize to the winner's account
-                let prize = T::Currency::free_balance(&Self::get_pallet_account());
-                T::Currency::transfer(
-                    &Self::get_pallet_account(), 
-                    &winner, 
-                    prize, 
-                    ExistenceRequirement::AllowDeath)?;
-
-                // 5. Resets the participants list, and gets ready for another lottery round
-                Participants::<T>::kill();
-
-                // 6. Notify the event
-                Self::deposit_event(Event::PrizeAwarded { winner: winner.clone() } );
-            }, 
-            None => {
-                // Notify the event (No participants)
-                Self::deposit_event(Event::ThereAreNoParticipants);
+        // 2. Checks that the sales deadline has passed, unless root is allowed 
+        // to draw early, as an escape hatch for the governance
+        if !Self::sales_closed(lottery_id) {
+            ensure!(T::AllowEarlyRootDraw::get(), Error::<T>::DrawNotYetAllowed);
+            if let Some(sales_end) = Self::get_round_end(lottery_id) {
+                let round = Self::get_current_round(lottery_id);
+                Self::deposit_event(Event::EarlyDrawForced { lottery_id, round, sales_end });
             }
+        }
+
+        // 3. Draws the winner and awards the prize immediately. The lotteries
+        // drawn by commit and reveal still require the seed
+        let participants = Self::participant_count(lottery_id);
+        Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)?;
+
+        // 4. Charges the weight of the participants actually drawn over, and
+        // nothing at all when there were none and the round was only rolled over
+        let actual_weight = Some(T::WeightInfo::award_prize(participants));
+        if participants == 0 {
+            return Ok(PostDispatchInfo { actual_weight, pays_fee: Pays::No });
+        }
+        Ok(actual_weight.into())
+    }
+
+    // Draws a round left undrawn for more than the stale threshold past the end 
+    // of its ticket sales, or past its start when the sales have no end, as when
+    // the chain halted over its scheduled or automatic draw. It's the recovery 
+    // for the rounds reported stale, and can't draw the rounds whose deadline
+    // passed more recently. A round without enough participants to be drawn 
+    // is refunded instead
+    #[pallet::call_index(38)]
+    #[pallet::weight(
+        T::WeightInfo::force_settle_stale_round(T::MaxParticipants::get())
+            .max(T::WeightInfo::cancel_lottery(T::MaxParticipants::get()))
+    )]
+    pub fn force_settle_stale_round(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature
+        ensure_root(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+        ensure!(!Self::get_round_drawn(lottery_id), Error::<T>::RoundAlreadyDrawn);
+
+        // 2. Checks that the round is stale
+        let threshold = T::StaleRoundBlocks::get();
+        let since = Self::get_round_end(lottery_id)
+            .or_else(|| Self::get_round_start(lottery_id))
+            .ok_or(Error::<T>::RoundNotStale)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(
+            !threshold.is_zero() && now.saturating_sub(since) > threshold,
+            Error::<T>::RoundNotStale
+        );
+
+        // 3. Draws the winner and awards the prize immediately, as the normal 
+        // draw would, or refunds the participants when there are too few of them
+        let round = Self::get_current_round(lottery_id);
+        let participants = Self::participant_count(lottery_id);
+        let actual_weight = if participants > 0 && participants < T::MinParticipants::get() {
+            let refunded = Self::refund_round(lottery_id);
+            Self::deposit_event(Event::RoundRefundedInsufficientParticipants { lottery_id, round, refunded });
+            T::WeightInfo::cancel_lottery(participants)
+        } else {
+            Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)?;
+            T::WeightInfo::force_settle_stale_round(participants)
         };
 
+        // 4. Notify the event
+        Self::deposit_event(Event::StaleRoundSettled { lottery_id, round });
+        Ok(Some(actual_weight).into())
+    }
+
+    #[pallet::call_index(2)]
+    #[pallet::weight(T::WeightInfo::set_ticket_cost())]
+    pub fn set_ticket_cost(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        new_cost: BalanceOf<T>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Checks that the new cost is not zero nor below the minimum
+        ensure!(!new_cost.is_zero(), Error::<T>::InvalidTicketCost);
+        ensure!(new_cost >= T::MinTicketCost::get(), Error::<T>::TicketCostTooLow);
+
+        // 3. Stores the new cost, to be charged from now on
+        let old = Lotteries::<T>::try_mutate(lottery_id, |lottery| {
+            let lottery = lottery.as_mut().ok_or(Error::<T>::LotteryNotFound)?;
+            Ok::<_, Error<T>>(mem::replace(&mut lottery.ticket_cost, new_cost))
+        })?;
+
+        // 4. Notify the event
+        Self::deposit_event(Event::TicketCostChanged { lottery_id, old, new: new_cost });
+        Ok(())
+    }
+
+    // Sets what the prize of the lottery's current round is made of, before any
+    // ticket is sold so the participants know what they enter for. A fixed 
+    // prize is to be funded with donations before the round is drawn. The round
+    // after it goes back to the pool
+    #[pallet::call_index(39)]
+    #[pallet::weight(T::WeightInfo::set_prize_source())]
+    pub fn set_prize_source(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        source: PrizeSource<BalanceOf<T>>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Checks that the round has not started, and that a fixed prize is 
+        // worth winning
+        ensure!(Self::participant_count(lottery_id) == 0, Error::<T>::RoundAlreadyStarted);
+        if let PrizeSource::Fixed { amount } = source {
+            ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+        }
+
+        // 3. Stores the source for the current round
+        RoundPrizeSource::<T>::insert(lottery_id, source);
+
+        // 4. Notify the event
+        let round = Self::get_current_round(lottery_id);
+        Self::deposit_event(Event::PrizeSourceSet { lottery_id, round, source });
+        Ok(())
+    }
+
+    #[pallet::call_index(25)]
+    #[pallet::weight(T::WeightInfo::set_max_participants())]
+    pub fn set_max_participants(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        new_cap: u32,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Checks that the new cap is within the bound of the participants list,
+        // and that it still fits the participants of the current round
+        ensure!(
+            new_cap > 0 && new_cap <= T::MaxParticipants::get(),
+            Error::<T>::InvalidMaxParticipants
+        );
+        ensure!(new_cap >= Self::participant_count(lottery_id), Error::<T>::CapBelowCurrent);
+
+        // 3. Stores the new cap, to be checked on the next purchases
+        let old = Lotteries::<T>::try_mutate(lottery_id, |lottery| {
+            let lottery = lottery.as_mut().ok_or(Error::<T>::LotteryNotFound)?;
+            Ok::<_, Error<T>>(mem::replace(&mut lottery.max_participants, new_cap))
+        })?;
+
+        // 4. Notify the event
+        Self::deposit_event(Event::MaxParticipantsChanged { lottery_id, old, new: new_cap });
+        Ok(())
+    }
+
+    #[pallet::call_index(3)]
+    #[pallet::weight(T::WeightInfo::cancel_lottery(T::MaxParticipants::get()))]
+    pub fn cancel_lottery(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let _who = ensure_root(origin)?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Refunds every participant, and gets ready for another lottery round
+        let refunded = Self::refund_round(lottery_id);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::LotteryCancelled { lottery_id, refunded });
+
+        // 4. Slashes the creation deposit, if the lottery was never drawn
+        Self::slash_creation_deposit(lottery_id)
+    }
+
+    // Cancels the current round as `cancel_lottery` does, refunding up to the
+    // given number of participants on each call, so the rounds with more 
+    // participants than fit a block can be cancelled. The round is only reset 
+    // once every participant is refunded, and can't be drawn nor sell tickets
+    // in the meantime
+    #[pallet::call_index(33)]
+    #[pallet::weight(T::WeightInfo::cancel_lottery_paged((*limit).min(T::MaxParticipants::get())))]
+    pub fn cancel_lottery_paged(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        limit: u32,
+    ) -> DispatchResultWithPostInfo {
+
+        // 1. Validates the origin signature
+        ensure_root(origin)?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Refunds the participants of the page, taken from the end of the list
+        let page = Self::take_last_participants(lottery_id, limit);
+        let (mut refunded, mut missing) = CancellationProgress::<T>::take(lottery_id).unwrap_or_default();
+        Self::refund_participants(lottery_id, &page, &mut refunded, &mut missing);
+        let actual_weight = Some(T::WeightInfo::cancel_lottery_paged(page.len() as u32));
+
+        // 3. Keeps track of the progress while participants remain to be refunded,
+        // forgetting the tickets of the participants refunded
+        let remaining = Self::participant_count(lottery_id);
+        if remaining > 0 {
+            for (who, _) in page.iter() {
+                Self::void_tickets(lottery_id, who);
+                TicketMemos::<T>::remove(lottery_id, who);
+                #[cfg(feature = "nfts")]
+                TicketItems::<T>::remove(lottery_id, who);
+            }
+            CancellationProgress::<T>::insert(lottery_id, (refunded, missing));
+            Self::deposit_event(Event::CancellationProgressed { lottery_id, refunded, remaining });
+            return Ok(actual_weight.into());
+        }
+
+        // 4. Gets ready for another lottery round once everyone is refunded
+        Self::close_refunded_round(lottery_id, missing);
+        Self::deposit_event(Event::LotteryCancelled { lottery_id, refunded });
+
+        // 5. Slashes the creation deposit, if the lottery was never drawn
+        Self::slash_creation_deposit(lottery_id)?;
+        Ok(actual_weight.into())
+    }
+
+    #[pallet::call_index(7)]
+    #[pallet::weight(T::WeightInfo::add_to_allowlist())]
+    pub fn add_to_allowlist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Allows the account to buy tickets
+        if !AllowList::<T>::contains_key(&who) {
+            AllowList::<T>::insert(&who, ());
+            AllowListCount::<T>::mutate(|count| count.saturating_inc());
+        }
+
+        // 3. Notify the event
+        Self::deposit_event(Event::AddedToAllowList { who });
+        Ok(())
+    }
+
+    #[pallet::call_index(8)]
+    #[pallet::weight(T::WeightInfo::remove_from_allowlist())]
+    pub fn remove_from_allowlist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Stops the account from buying new tickets. Tickets already 
+        // bought are kept until the round is over
+        if AllowList::<T>::take(&who).is_some() {
+            AllowListCount::<T>::mutate(|count| count.saturating_dec());
+        }
+
+        // 3. Notify the event
+        Self::deposit_event(Event::RemovedFromAllowList { who });
+        Ok(())
+    }
+
+    // Allows every account of the batch to buy tickets. The accounts already
+    // in the allowlist are skipped
+    #[pallet::call_index(36)]
+    #[pallet::weight(T::WeightInfo::add_to_allowlist_batch(who.len() as u32))]
+    pub fn add_to_allowlist_batch(
+        origin: OriginFor<T>,
+        who: BoundedVec<T::AccountId, T::MaxBatch>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Allows each account to buy tickets
+        let mut added = 0u32;
+        for account in who.iter() {
+            if !AllowList::<T>::contains_key(account) {
+                AllowList::<T>::insert(account, ());
+                added.saturating_inc();
+            }
+        }
+        AllowListCount::<T>::mutate(|count| *count = count.saturating_add(added));
+
+        // 3. Notify the event
+        Self::deposit_event(Event::AllowListUpdated { added, removed: 0 });
+        Ok(())
+    }
+
+    // Stops every account of the batch from buying new tickets. The accounts 
+    // not in the allowlist are skipped
+    #[pallet::call_index(37)]
+    #[pallet::weight(T::WeightInfo::remove_from_allowlist_batch(who.len() as u32))]
+    pub fn remove_from_allowlist_batch(
+        origin: OriginFor<T>,
+        who: BoundedVec<T::AccountId, T::MaxBatch>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Removes each account, keeping the tickets already bought until 
+        // the round is over
+        let mut removed = 0u32;
+        for account in who.iter() {
+            if AllowList::<T>::take(account).is_some() {
+                removed.saturating_inc();
+            }
+        }
+        AllowListCount::<T>::mutate(|count| *count = count.saturating_sub(removed));
+
+        // 3. Notify the event
+        Self::deposit_event(Event::AllowListUpdated { added: 0, removed });
+        Ok(())
+    }
+
+    #[pallet::call_index(27)]
+    #[pallet::weight(T::WeightInfo::add_fee_exempt())]
+    pub fn add_fee_exempt(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Exempts the account from the fee on the prizes it wins
+        FeeExempt::<T>::insert(&who, ());
+
+        // 3. Notify the event
+        Self::deposit_event(Event::FeeExemptAdded { who });
+        Ok(())
+    }
+
+    #[pallet::call_index(28)]
+    #[pallet::weight(T::WeightInfo::remove_fee_exempt())]
+    pub fn remove_fee_exempt(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Charges the fee again on the next prizes the account wins
+        FeeExempt::<T>::remove(&who);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::FeeExemptRemoved { who });
+        Ok(())
+    }
+
+    #[pallet::call_index(9)]
+    #[pallet::weight(T::WeightInfo::commit_seed())]
+    pub fn commit_seed(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        hash: [u8; 32],
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is the oracle
+        T::SeedOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Checks that the current round has no seed yet
+        ensure!(
+            !SeedCommitments::<T>::contains_key(lottery_id),
+            Error::<T>::SeedAlreadyCommitted
+        );
+
+        // 3. Stores the hash of the seed, so the round can only be drawn 
+        // by revealing it
+        SeedCommitments::<T>::insert(lottery_id, hash);
+
+        // 4. Notify the event
+        let round = Self::get_current_round(lottery_id);
+        Self::deposit_event(Event::SeedCommitted { lottery_id, round });
+        Ok(())
+    }
+
+    #[pallet::call_index(10)]
+    #[pallet::weight(T::WeightInfo::reveal_and_draw(T::MaxParticipants::get()))]
+    pub fn reveal_and_draw(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        seed: [u8; 32],
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is the oracle
+        T::SeedOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Checks that the seed matches the hash committed for the round
+        let commitment = Self::get_seed_commitment(lottery_id)
+            .ok_or(Error::<T>::SeedNotCommitted)?;
+        ensure!(blake2_256(&seed) == commitment, Error::<T>::InvalidSeed);
+
+        // 3. Checks that the sales deadline has passed
+        ensure!(
+            Self::get_round_end(lottery_id).is_some() && Self::sales_closed(lottery_id),
+            Error::<T>::DrawNotYetAllowed
+        );
+
+        // 4. Notify the event
+        let round = Self::get_current_round(lottery_id);
+        Self::deposit_event(Event::SeedRevealed { lottery_id, round, seed });
+
+        // 5. Draws the winner mixing the seed with the on-chain randomness, 
+        // and awards the prize
+        Self::do_award_prize(lottery_id, DrawSeed::Revealed(seed), None)
+    }
+
+    #[pallet::call_index(11)]
+    #[pallet::weight(T::WeightInfo::donate())]
+    pub fn donate(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let who = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Checks that something is being donated, within the funds the 
+        // lottery can hold
+        ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+        Self::ensure_escrow_room(lottery_id, amount)?;
+
+        // 3. Transfers the donation to the lottery's account, to be included 
+        // in the next prize. The donor is not registered as a participant
+        Self::deposit_to_pot(lottery_id, &who, amount)?;
+        JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(amount));
+
+        // 4. Notify the event
+        Self::deposit_event(Event::Donated { lottery_id, who, amount });
+        Ok(())
+    }
+
+    #[pallet::call_index(24)]
+    #[pallet::weight(T::WeightInfo::scratch())]
+    pub fn scratch(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let who = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+        ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+
+        // 2. Checks that the randomness is old enough, and that the lottery's
+        // account can pay the scratch prize without touching the prizes owed 
+        // to the winners of previous rounds
+        ensure!(Self::randomness_ready(DrawSeed::OnChain), Error::<T>::RandomnessNotReady);
+        let prize = T::ScratchPrize::get();
+        ensure!(Self::available_pot(lottery_id) >= prize, Error::<T>::PotTooSmall);
+
+        // 3. Queues the scratch to be rolled on a later block, at least the
+        // randomness delay after this one, as the randomness known on this block
+        // would let the user check the outcome before buying it
+        let settles_at = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::RandomnessDelay::get())
+            .saturating_add(1u32.into());
+        PendingScratches::<T>::try_mutate(settles_at, |pending| pending.try_push((lottery_id, who.clone())))
+            .map_err(|_| Error::<T>::TooManyScratches)?;
+
+        // 4. Charges the scratch, which is independent of the round and
+        // doesn't make the user a participant. Its cost is added to the pot
+        let cost = T::ScratchCost::get();
+        Self::deposit_to_pot(lottery_id, &who, cost)?;
+        JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(cost));
+
+        // 5. Notify the event
+        Self::deposit_event(Event::ScratchBought { lottery_id, who, settles_at });
+        Ok(())
+    }
+
+    #[pallet::call_index(12)]
+    #[pallet::weight(T::WeightInfo::claim_prize())]
+    pub fn claim_prize(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let winner = ensure_signed(origin)?;
+
+        // 2. Pays out the prize owed to the winner, without waiting for the 
+        // payouts done on idle blocks
+        Self::do_claim_prize(lottery_id, winner)
+    }
+
+    #[pallet::call_index(13)]
+    #[pallet::weight(T::WeightInfo::sweep_expired(*max_prizes))]
+    pub fn sweep_expired(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        max_prizes: u32,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin signature, any account can sweep the prizes
+        let _who = ensure_signed(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Looks for the expired prizes among the given number of unclaimed prizes
+        let expired: Vec<_> = UnclaimedPrizes::<T>::iter_prefix(lottery_id)
+            .take(max_prizes as usize)
+            .filter(|(_, (_, drawn_at))| Self::prize_expired(*drawn_at))
+            .map(|(who, (amount, _))| (who, amount))
+            .collect();
+
+        // 3. Moves the expired prizes back into the pot, as a jackpot carried 
+        // over to the next round
+        for (who, amount) in expired {
+            Self::expire_prize(lottery_id, who, amount);
+        }
         Ok(())
     }
-}
+
+    #[pallet::call_index(16)]
+    #[pallet::weight(T::WeightInfo::pause())]
+    pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Stops buying and leaving tickets, and drawing the prizes. The 
+        // lotteries can still be cancelled and the prizes claimed, so the 
+        // funds can always be returned
+        Paused::<T>::put(true);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::LotteryPaused);
+        Ok(())
+    }
+
+    #[pallet::call_index(17)]
+    #[pallet::weight(T::WeightInfo::unpause())]
+    pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Resumes the lotteries
+        Paused::<T>::put(false);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::LotteryUnpaused);
+        Ok(())
+    }
+
+    // Turns the lotteries on, which the other calls require
+    #[pallet::call_index(31)]
+    #[pallet::weight(T::WeightInfo::enable_lottery())]
+    pub fn enable_lottery(origin: OriginFor<T>) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        T::AdminOrigin::ensure_origin(origin)?;
+
+        // 2. Turns the lotteries on
+        Enabled::<T>::put(true);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::LotteryEnabled);
+        Ok(())
+    }
+
+    // Turns the lotteries off, leaving every call but enabling them unavailable,
+    // except the ones giving funds back: leaving the rounds, reducing tickets, 
+    // unsubscribing, cancelling the lotteries and claiming the prizes. Unlike a
+    // pause, it's meant for the deployment before any lottery runs, but those 
+    // calls keep the funds of any round in progress from being locked
+    #[pallet::call_index(32)]
+    #[pallet::weight(T::WeightInfo::disable_lottery())]
+    pub fn disable_lottery(origin: OriginFor<T>) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        T::AdminOrigin::ensure_origin(origin)?;
+
+        // 2. Turns the lotteries off
+        Enabled::<T>::put(false);
+
+        // 3. Notify the event
+        Self::deposit_event(Event::LotteryDisabled);
+        Ok(())
+    }
+
+    // Registers the account with a single ticket without charging it, as an escape 
+    // hatch for support cases such as a purchase paid but not registered
+    #[pallet::call_index(21)]
+    #[pallet::weight(T::WeightInfo::force_add_participant(T::MaxParticipants::get()))]
+    pub fn force_add_participant(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        who: T::AccountId,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+        let lottery = Self::get_lottery(lottery_id).ok_or(Error::<T>::LotteryNotFound)?;
+        ensure!(
+            who != Self::get_pallet_account(lottery_id),
+            Error::<T>::InvalidParticipant
+        );
+
+        // 2. Registers the account as a new participant, within the participants cap
+        ensure!(
+            Self::tickets_held(lottery_id, &who).is_none(),
+            Error::<T>::AccountAlreadyParticipating
+        );
+        let count = Self::participant_count(lottery_id);
+        ensure!(count < lottery.max_participants, Error::<T>::LotteryFull);
+        let round_started = count == 0;
+        if round_started {
+            Self::start_sales(lottery_id);
+        }
+        Self::set_tickets(lottery_id, &who, 1)?;
+        Self::issue_tickets(lottery_id, &who, 1)?;
+        log::info!(target: LOG_TARGET, "Force added {:?} to lottery {}", who, lottery_id);
+
+        // 3. Notify the events
+        if round_started {
+            let round = Self::get_current_round(lottery_id);
+            Self::deposit_event(Event::RoundStarted { lottery_id, round });
+        }
+        Self::deposit_event(Event::ParticipantForceAdded { lottery_id, who });
+        Ok(())
+    }
+
+    // Removes the participant and all of its tickets without refunding them, as 
+    // an escape hatch for support cases. The funds held for its tickets are
+    // collected into the pot
+    #[pallet::call_index(22)]
+    #[pallet::weight(T::WeightInfo::force_remove_participant(T::MaxParticipants::get()))]
+    pub fn force_remove_participant(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        who: T::AccountId,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin is allowed to manage the lottery
+        T::AdminOrigin::ensure_origin(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Removes the participant along with its tickets
+        ensure!(Self::tickets_held(lottery_id, &who).is_some(), Error::<T>::NotParticipating);
+        Self::set_tickets(lottery_id, &who, 0)?;
+        if Self::participant_count(lottery_id) == 0 {
+            Self::clear_round_end(lottery_id);
+            Self::clear_tickets(lottery_id);
+        } else {
+            Self::void_tickets(lottery_id, &who);
+        }
+        TicketMemos::<T>::remove(lottery_id, &who);
+        #[cfg(feature = "nfts")]
+        TicketItems::<T>::remove(lottery_id, &who);
+
+        // 3. Collects the funds held for its tickets into the pot
+        let held = ReservedFunds::<T>::take(lottery_id, &who);
+        let collected = Self::collect_ticket_funds(lottery_id, &who, held)?;
+        log::info!(
+            target: LOG_TARGET,
+            "Force removed {:?} from lottery {}, collecting {:?} into the pot",
+            who,
+            lottery_id,
+            collected,
+        );
+
+        // 4. Notify the event
+        Self::deposit_event(Event::ParticipantForceRemoved { lottery_id, who });
+        Ok(())
+    }
+
+    // Resets the nonce of the random numbers. The nonce only keeps consecutive
+    // subjects apart within a block, and is combined with the parent block hash 
+    // and the randomness module's entropy, so starting it over doesn't make the 
+    // random numbers any more predictable
+    #[pallet::call_index(26)]
+    #[pallet::weight(T::WeightInfo::reset_nonce())]
+    pub fn reset_nonce(origin: OriginFor<T>) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        ensure_root(origin)?;
+        Self::ensure_enabled()?;
+
+        // 2. Starts the nonce over
+        let previous = Nonce::<T>::take();
+
+        // 3. Notify the event
+        Self::deposit_event(Event::NonceReset { previous });
+        Ok(())
+    }
+
+    // Recovers funds stuck in the lottery's account, such as funds transferred
+    // to it by hand or left behind by a failed migration. Up to the given amount
+    // is sent to the destination, never touching the funds carried over, owed
+    // to the winners or held for the tickets of the current round. It works
+    // while the lotteries are disabled too, as that's when stuck funds are
+    // most likely to be recovered
+    #[pallet::call_index(29)]
+    #[pallet::weight(T::WeightInfo::emergency_withdraw())]
+    pub fn emergency_withdraw(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        dest: T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        ensure_root(origin)?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Caps the amount at the funds not owed to anyone
+        let stray = Self::pot_balance(lottery_id).saturating_sub(Self::owed_funds(lottery_id));
+        let amount = amount.min(stray);
+        ensure!(!amount.is_zero(), Error::<T>::NoStrayFunds);
+
+        // 3. Sends the funds, keeping the lottery's account alive while it holds 
+        // owed funds
+        Self::pay_out(lottery_id, &dest, amount)?;
+
+        // 4. Notify the event
+        Self::deposit_event(Event::EmergencyWithdrawal { lottery_id, dest, amount });
+        Ok(())
+    }
+
+    // Draws the round using the given seed instead of the randomness module, so
+    // integration tests can assert the winners. Only compiled with the 
+    // `test-helpers` feature, which can't be enabled on a Wasm runtime
+    #[cfg(feature = "test-helpers")]
+    #[pallet::call_index(18)]
+    #[pallet::weight(T::WeightInfo::award_prize(T::MaxParticipants::get()))]
+    pub fn force_draw_with_seed(
+        origin: OriginFor<T>,
+        lottery_id: LotteryId,
+        seed: [u8; 32],
+    ) -> DispatchResult {
+
+        // 1. Validates the origin signature
+        let _who = ensure_root(origin)?;
+        Self::ensure_enabled()?;
+        ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+        // 2. Draws the winner from the seed and awards the prize
+        Self::do_award_prize(lottery_id, DrawSeed::Fixed(seed), None)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,438 @@
+//! Storage migrations for the lottery example module
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::traits::{AccountIdConversion, Saturating, Zero},
+	storage_alias,
+	traits::{
+		Currency, ExistenceRequirement, GetStorageVersion, NamedReservableCurrency, OnRuntimeUpgrade, 
+		ReservableCurrency, StorageVersion,
+	},
+};
+use scale_info::prelude::vec::Vec;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::sp_runtime::TryRuntimeError;
+
+/// Every migration of the module, in the order they are to be applied
+pub type Migrations<T> = (
+	v1::MigrateToV1<T>,
+	v2::MigrateToV2<T>,
+	v3::MigrateToV3<T>,
+	v4::MigrateToV4<T>,
+	v5::MigrateToV5<T>,
+);
+
+pub mod v1 {
+	use super::*;
+
+	// The participants of the single lottery kept before version 1, holding an
+	// entry for each ticket bought, with every ticket cost transferred to the
+	// module's account
+	#[storage_alias]
+	pub(super) type Participants<T: Config> = StorageValue<
+		Pallet<T>,
+		BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxParticipants>,
+		OptionQuery,
+	>;
+
+	/// Moves the single lottery kept before version 1 into a new lottery, turning
+	/// the entries of each participant into a ticket count and reserving the
+	/// tickets cost back on the participant's account. The ticket cost is derived
+	/// from the funds held, and the remainder is carried over to the next prize.
+	/// Those funds are expected in the native currency, the only one supported
+	/// before version 1
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+
+		fn on_runtime_upgrade() -> Weight {
+
+			// 1. Checks that the migration has not been applied yet
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				log::info!(target: LOG_TARGET, "Skipping the migration to v1, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			let mut weight = T::DbWeight::get().reads_writes(2, 2);
+			let Some(entries) = Participants::<T>::take() else {
+				return weight;
+			};
+
+			// 2. Counts the tickets of each participant, keeping the order in which
+			// the participants entered the lottery
+			let mut participants: Vec<(T::AccountId, u32)> = Vec::new();
+			for who in entries.iter() {
+				match participants.iter_mut().find(|(participant, _)| participant == who) {
+					Some((_, tickets)) => *tickets = tickets.saturating_add(1),
+					None => participants.push((who.clone(), 1)),
+				}
+			}
+
+			// 3. Derives the ticket cost from the funds held for the tickets
+			let old_account: T::AccountId = T::PalletId::get().into_account_truncating();
+			let funds = T::Currency::free_balance(&old_account);
+			let ticket_cost = funds / (entries.len() as u32).into();
+			weight.saturating_accrue(T::DbWeight::get().reads(1));
+			if ticket_cost.is_zero() {
+				log::error!(
+					target: LOG_TARGET,
+					"Dropping {} participants in the migration to v1, no funds held for the tickets",
+					participants.len(),
+				);
+				return weight;
+			}
+
+			// 4. Creates the new lottery
+			let lottery_id = Pallet::<T>::get_next_lottery_id();
+			let lottery_account = Pallet::<T>::get_pallet_account(lottery_id);
+			Lotteries::<T>::insert(lottery_id, LotteryConfig {
+				ticket_cost,
+				max_participants: T::MaxParticipants::get(),
+			});
+			NextLotteryId::<T>::put(lottery_id.saturating_add(1));
+			weight.saturating_accrue(T::DbWeight::get().writes(2));
+
+			// 5. Gives back the tickets cost to each participant, reserving it on its
+			// account as on any purchase. Participants that can't be moved are
+			// dropped, and their funds are carried over
+			participants.retain(|(who, tickets)| {
+				let cost = ticket_cost.saturating_mul((*tickets).into());
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 3));
+				let moved = T::Currency::transfer(
+					&old_account,
+					who,
+					cost,
+					ExistenceRequirement::AllowDeath)
+					.and_then(|_| T::Currency::reserve(who, cost));
+				match moved {
+					Ok(()) => {
+						ReservedFunds::<T>::insert(lottery_id, who, cost);
+						true
+					},
+					Err(error) => {
+						log::warn!(
+							target: LOG_TARGET,
+							"Dropping {:?} in the migration to v1: {:?}",
+							who,
+							error,
+						);
+						false
+					},
+				}
+			});
+			let participants: BoundedVec<_, T::MaxParticipants> = participants
+				.try_into()
+				.expect("there are not more participants than entries; qed");
+			for (who, tickets) in participants.iter() {
+				let stored = Pallet::<T>::set_tickets(lottery_id, who, *tickets)
+					.and_then(|_| Pallet::<T>::issue_tickets(lottery_id, who, *tickets));
+				if let Err(error) = stored {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to store the tickets of {:?} in the migration to v1: {:?}",
+						who,
+						error,
+					);
+				}
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, (*tickets).saturating_add(1).into()));
+			}
+
+			// 6. Moves the remaining funds to the new lottery's account
+			let remainder = T::Currency::free_balance(&old_account);
+			if !remainder.is_zero() && T::Currency::transfer(
+				&old_account,
+				&lottery_account,
+				remainder,
+				ExistenceRequirement::AllowDeath).is_ok() {
+				JackpotCarryover::<T>::insert(lottery_id, remainder);
+			}
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 4));
+
+			log::info!(target: LOG_TARGET, "Migrated the lottery to v1 as lottery {}", lottery_id);
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let entries = Participants::<T>::get().unwrap_or_default().into_inner();
+			Ok((Pallet::<T>::get_next_lottery_id(), entries).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (lottery_id, entries): (LotteryId, Vec<T::AccountId>) =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the old participants")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 1, "Storage version not bumped");
+			ensure!(Participants::<T>::get().is_none(), "Old participants list not removed");
+
+			// Every old participant keeps its tickets in the new lottery
+			let participants = Pallet::<T>::participants_of(lottery_id);
+			ensure!(
+				participants.iter().all(|(who, tickets)| {
+					entries.iter().filter(|entry| *entry == who).count() as u32 == *tickets
+				}),
+				"Participant's tickets don't match the old entries"
+			);
+			ensure!(
+				entries.iter().all(|entry| participants.iter().any(|(who, _)| who == entry)),
+				"Old participant missing after the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	// The winners history kept before version 2, as (round, winner, prize)
+	#[storage_alias]
+	pub(super) type WinnerHistory<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		LotteryId,
+		BoundedVec<
+			(u32, <T as frame_system::Config>::AccountId, BalanceOf<T>),
+			<T as Config>::MaxHistory,
+		>,
+		ValueQuery,
+	>;
+
+	/// Turns each entry of the winners history into a record holding the round's 
+	/// fee, burn, pot and participants. Those were not kept before version 2, so
+	/// the migrated entries have them set to zero
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+
+		fn on_runtime_upgrade() -> Weight {
+
+			// 1. Checks that the migration has not been applied yet
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				log::info!(target: LOG_TARGET, "Skipping the migration to v2, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			// 2. Rewrites each lottery's history in the new format
+			crate::WinnerHistory::<T>::translate::<
+				BoundedVec<(u32, T::AccountId, BalanceOf<T>), T::MaxHistory>,
+				_,
+			>(|_, history| {
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				let records: Vec<_> = history
+					.into_iter()
+					.map(|(round, winner, prize)| WinnerRecord {
+						round,
+						winner,
+						prize,
+						fee: Zero::zero(),
+						burned: Zero::zero(),
+						pot: Zero::zero(),
+						participants_hash: Default::default(),
+						participant_count: 0,
+					})
+					.collect();
+				Some(BoundedVec::truncate_from(records))
+			});
+
+			log::info!(target: LOG_TARGET, "Migrated the winners history to v2");
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			// The history is only in the old format before version 2
+			if Pallet::<T>::on_chain_storage_version() > 1 {
+				return Ok(Vec::<(LotteryId, u32)>::new().encode());
+			}
+			let entries: Vec<(LotteryId, u32)> = WinnerHistory::<T>::iter()
+				.map(|(lottery_id, history)| (lottery_id, history.len() as u32))
+				.collect();
+			Ok(entries.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let entries: Vec<(LotteryId, u32)> =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the old history")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "Storage version not bumped");
+
+			// Every lottery keeps as many winners as it had before the migration
+			ensure!(
+				entries.iter().all(|(lottery_id, count)| {
+					Pallet::<T>::get_winner_history(lottery_id).len() as u32 == *count
+				}),
+				"Winners missing from the history after the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Turns the lotteries on for the chains already running them. The lotteries 
+	/// are dormant until enabled since version 3, which would otherwise stop the
+	/// rounds in progress
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+
+		fn on_runtime_upgrade() -> Weight {
+
+			// 1. Checks that the migration has not been applied yet
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				log::info!(target: LOG_TARGET, "Skipping the migration to v3, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			// 2. Keeps the lotteries running
+			Enabled::<T>::put(true);
+
+			log::info!(target: LOG_TARGET, "Migrated the lotteries to v3, enabled");
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Pallet::<T>::on_chain_storage_version() < 3).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let migrated: bool =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the migration state")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 3, "Storage version not bumped");
+			ensure!(!migrated || Pallet::<T>::get_enabled(), "Lotteries not enabled by the migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Counts the accounts in the allowlist, which were not counted before 
+	/// version 4
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+
+		fn on_runtime_upgrade() -> Weight {
+
+			// 1. Checks that the migration has not been applied yet
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				log::info!(target: LOG_TARGET, "Skipping the migration to v4, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::new(4).put::<Pallet<T>>();
+
+			// 2. Counts the accounts in the allowlist
+			let count = AllowList::<T>::iter_keys().count() as u32;
+			AllowListCount::<T>::put(count);
+
+			log::info!(target: LOG_TARGET, "Migrated the allowlist to v4, {} accounts counted", count);
+			T::DbWeight::get().reads_writes(u64::from(count).saturating_add(1), 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((AllowList::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the allowlist count")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 4, "Storage version not bumped");
+			ensure!(Pallet::<T>::get_allowlist_count() == count, "Allowlist count doesn't match its accounts");
+			Ok(())
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	// Moves the funds reserved on the account from the plain reserve into the 
+	// module's named reserve, returning the amount moved
+	fn move_to_named<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		let moved = amount.saturating_sub(T::Currency::unreserve(who, amount));
+		if T::Currency::reserve_named(&Pallet::<T>::reserve_id(), who, moved).is_err() {
+			log::warn!(target: LOG_TARGET, "Failed to move the reserve of {:?} in the migration to v5", who);
+			return Zero::zero();
+		}
+		moved
+	}
+
+	/// Moves the funds reserved by the module into a reserve named after its 
+	/// `PalletId`. The ticket funds, the funds prepaid by the subscribers, and the 
+	/// creation deposits were reserved along with the funds of other modules 
+	/// before version 5, so a draw could take the reserves of any of them
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+
+		fn on_runtime_upgrade() -> Weight {
+
+			// 1. Checks that the migration has not been applied yet
+			if Pallet::<T>::on_chain_storage_version() != 4 {
+				log::info!(target: LOG_TARGET, "Skipping the migration to v5, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::new(5).put::<Pallet<T>>();
+			let mut moves = 0u64;
+
+			// 2. Moves the ticket funds and the prepaid funds, which are only reserved
+			// when paid in the native currency
+			#[cfg(not(feature = "fungibles"))]
+			{
+				for (_, who, held) in ReservedFunds::<T>::iter() {
+					move_to_named::<T>(&who, held);
+					moves.saturating_inc();
+				}
+				for (_, who, subscription) in Subscriptions::<T>::iter() {
+					move_to_named::<T>(&who, subscription.funds);
+					moves.saturating_inc();
+				}
+			}
+
+			// 3. Moves the creation deposits, keeping what could actually be moved
+			CreationDeposits::<T>::translate::<(T::AccountId, BalanceOf<T>), _>(|_, (creator, deposit)| {
+				moves.saturating_inc();
+				let moved = move_to_named::<T>(&creator, deposit);
+				(!moved.is_zero()).then_some((creator, moved))
+			});
+
+			log::info!(target: LOG_TARGET, "Migrated the reserves to v5, {} reserves moved", moves);
+			T::DbWeight::get().reads_writes(moves.saturating_mul(3).saturating_add(1), moves.saturating_mul(2).saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Pallet::<T>::on_chain_storage_version() < 5).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let migrated: bool =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the migration state")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 5, "Storage version not bumped");
+
+			// Every creation deposit is held in the named reserve
+			ensure!(
+				!migrated || CreationDeposits::<T>::iter_values().all(|(creator, deposit)| {
+					T::Currency::reserved_balance_named(&Pallet::<T>::reserve_id(), &creator) >= deposit
+				}),
+				"Creation deposit missing from the named reserve"
+			);
+			Ok(())
+		}
+	}
+}
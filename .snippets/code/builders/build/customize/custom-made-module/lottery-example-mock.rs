@@ -0,0 +1,288 @@
+//! Test environment for the lottery example module
+
+use crate as pallet_lottery_example;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstBool, ConstU32, ConstU64, Everything, InstanceFilter, Randomness, UnixTime},
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	BuildStorage, Perbill, Permill, RuntimeDebug,
+};
+
+// The accounts are 16 bytes wide, so the sub-account of each lottery keeps the
+// whole module id and lottery id, and the lotteries don't share an account
+pub type AccountId = u128;
+pub type Balance = u64;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Proxy: pallet_proxy,
+		Lottery: pallet_lottery_example,
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const TREASURY: AccountId = 99;
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+// The settings the tests change, each test running on its own thread with the
+// default values
+parameter_types! {
+	pub static ExistentialDeposit: Balance = 1;
+	pub static LotteryWinnersPerRound: u32 = 1;
+	pub static LotteryBurnPercent: Permill = Permill::zero();
+	pub static LotterySeedNextRoundPercent: Permill = Permill::zero();
+	pub static LotteryMaxPrize: Option<Balance> = None;
+	pub static LotteryMaxEscrow: Option<Balance> = None;
+	pub static LotteryMinTicketCost: Balance = 1;
+	pub static LotteryDustDestination: pallet_lottery_example::DustDestination =
+		pallet_lottery_example::DustDestination::Winner;
+	pub static LotteryDuplicatePurchase: pallet_lottery_example::DuplicatePurchase =
+		pallet_lottery_example::DuplicatePurchase::AllowMultiple;
+	pub static LotteryScratchOdds: Perbill = Perbill::from_percent(1);
+	pub static Now: u64 = 0;
+	pub static LotteryPalletId: PalletId = PalletId(*b"loex5678");
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+}
+
+// The proxies of the tests, allowed to make any call, or to buy tickets only
+#[derive(
+	Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Default,
+)]
+pub enum ProxyType {
+	#[default]
+	Any,
+	BuyTicket,
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, c: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::BuyTicket => {
+				matches!(c, RuntimeCall::Lottery(pallet_lottery_example::Call::buy_ticket { .. }))
+			},
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		self == o || *self == ProxyType::Any
+	}
+}
+
+impl pallet_proxy::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ConstU64<1>;
+	type ProxyDepositFactor = ConstU64<1>;
+	type MaxProxies = ConstU32<4>;
+	type WeightInfo = ();
+	type MaxPending = ConstU32<2>;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = ConstU64<1>;
+	type AnnouncementDepositFactor = ConstU64<1>;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = sp_runtime::testing::TestXt<RuntimeCall, ()>;
+	type OverarchingCall = RuntimeCall;
+}
+
+// Derives the randomness from the subject alone, so the draws are reproducible
+// while still differing from one nonce to the next
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		(BlakeTwo256::hash(subject), 0)
+	}
+}
+
+// Derives the VRF randomness from the subject too, as the output of the block
+// before the current one, so a draw on it is told apart from a draw on the
+// randomness module
+#[cfg(feature = "vrf-randomness")]
+pub struct TestVrfRandomness;
+
+#[cfg(feature = "vrf-randomness")]
+impl Randomness<Option<H256>, u64> for TestVrfRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, u64) {
+		let random = BlakeTwo256::hash(&(b"vrf", subject).encode());
+		(Some(random), System::block_number().saturating_sub(1))
+	}
+}
+
+// Reads the time of the block from the `Now` setting, in milliseconds
+pub struct TestTime;
+
+impl UnixTime for TestTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(Now::get())
+	}
+}
+
+parameter_types! {
+	pub const LotteryFeePercent: Permill = Permill::from_percent(5);
+	pub const LotteryFeeDestination: AccountId = TREASURY;
+	pub const LotteryPricing: pallet_lottery_example::PricingStrategy<Balance> =
+		pallet_lottery_example::PricingStrategy::Fixed;
+	pub const LotteryDrawSchedule: pallet_lottery_example::DrawSchedule<u64> =
+		pallet_lottery_example::DrawSchedule::Blocks(100);
+}
+
+impl pallet_lottery_example::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = LotteryPalletId;
+	type MaxLotteries = ConstU32<10>;
+	type LotteryCreationDeposit = ConstU64<0>;
+	type MaxParticipants = ConstU32<10>;
+	type MinParticipants = ConstU32<2>;
+	type MinPot = ConstU64<0>;
+	type DrawReward = ConstU64<0>;
+	type WinnersPerRound = LotteryWinnersPerRound;
+	type MaxHistory = ConstU32<10>;
+	type SalesDuration = ConstU64<10>;
+	type DrawSchedule = LotteryDrawSchedule;
+	type Timestamp = TestTime;
+	type MyRandomness = TestRandomness;
+	#[cfg(feature = "vrf-randomness")]
+	type ValidatorRandomness = TestVrfRandomness;
+	type RandomnessDelay = ConstU64<0>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type FeePercent = LotteryFeePercent;
+	type FeeDestination = LotteryFeeDestination;
+	type WeightInfo = ();
+	type RequireAllowList = ConstBool<false>;
+	type MaxTicketsPerAccount = ConstU32<1_000>;
+	type SeedOrigin = EnsureRoot<AccountId>;
+	type ClaimDeadline = ConstU64<100>;
+	type MaxPrize = LotteryMaxPrize;
+	type MaxEscrow = LotteryMaxEscrow;
+	type MaxBatch = ConstU32<10>;
+	type MaxMemo = ConstU32<32>;
+	type MixMemoEntropy = ConstBool<false>;
+	type OnWinner = ();
+	type Pricing = LotteryPricing;
+	type MaxTicketCost = ConstU64<{ Balance::MAX }>;
+	type MinTicketCost = LotteryMinTicketCost;
+	type UnsignedPriority = ConstU64<{ u64::MAX / 2 }>;
+	type UnsignedInterval = ConstU64<10>;
+	type RequireIdentity = ConstBool<false>;
+	type Identity = ();
+	type BurnPercent = LotteryBurnPercent;
+	type EligibilityCheck = ();
+	type ScratchCost = ConstU64<10>;
+	type ScratchOdds = LotteryScratchOdds;
+	type ScratchPrize = ConstU64<50>;
+	type MaxScratchesPerBlock = ConstU32<10>;
+	type DustDestination = LotteryDustDestination;
+	type StaleRoundBlocks = ConstU64<50>;
+	type SeedNextRoundPercent = LotterySeedNextRoundPercent;
+	type BuyCooldown = ConstU64<0>;
+	type EmitRoundSummary = ConstBool<true>;
+	type DuplicatePurchase = LotteryDuplicatePurchase;
+	type MaxAutoEntriesPerBlock = ConstU32<10>;
+	type MaxAutoDrawsPerBlock = ConstU32<5>;
+	type AllowEarlyRootDraw = ConstBool<false>;
+	type PityThreshold = ConstU32<0>;
+}
+
+// Builds the genesis of the tests, with the lotteries enabled and no lottery
+// created yet
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![(ALICE, 1_000), (BOB, 1_000), (CHARLIE, 1_000), (DAVE, 1_000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: self.balances }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		pallet_lottery_example::GenesisConfig::<Test> { enabled: true, ..Default::default() }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+
+		// Starts at the first block, as the events are only recorded from there
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ExtBuilder::default().build()
+}
@@ -0,0 +1,56 @@
+//! Runtime API definition for the lottery example module
+
+use codec::Codec;
+use scale_info::prelude::vec::Vec;
+use frame_support::sp_runtime::Perbill;
+
+use crate::{LotteryId, RoundInfo};
+
+sp_api::decl_runtime_apis! {
+	/// Read-only queries about the lotteries, for front-ends and light clients
+	pub trait LotteryApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the chance of the account to win the current round of the lottery
+		fn win_probability(lottery_id: LotteryId, who: AccountId) -> Perbill;
+
+		/// Returns the prize the current round of the lottery would award
+		fn current_pot(lottery_id: LotteryId) -> Balance;
+
+		/// Returns the number of accounts participating in the current round of the lottery
+		fn participant_count(lottery_id: LotteryId) -> u32;
+
+		/// Returns the number of tickets the account holds in the current round of the
+		/// lottery, which is zero if the account is not participating
+		fn tickets_of(lottery_id: LotteryId, who: AccountId) -> u32;
+
+		/// Returns the blake2_256 hash of the encoded accounts a past round of the lottery
+		/// was drawn over, sorted and concatenated, if it's still recorded
+		fn round_participants_hash(lottery_id: LotteryId, round: u32) -> Option<[u8; 32]>;
+
+		/// Returns a page of the winners of the lottery as (round, winner, prize), newest
+		/// first and skipping the `start` most recent ones, capped at a page size
+		fn winner_history(lottery_id: LotteryId, start: u32, limit: u32) -> Vec<(u32, AccountId, Balance)>;
+
+		/// Returns the state of the current round of the lottery, if the lottery exists
+		fn current_round_info(lottery_id: LotteryId) -> Option<RoundInfo<Balance, BlockNumber>>;
+
+		/// Returns the block the current round of the lottery is expected to be drawn at,
+		/// if it has participants and is drawn at a known block
+		fn next_draw_at(lottery_id: LotteryId) -> Option<BlockNumber>;
+
+		/// Returns the number of accounts in the allowlist
+		fn allowlist_count() -> u32;
+
+		/// Returns the tickets sold by every lottery since genesis, without the refunded ones
+		fn total_tickets_sold() -> u64;
+
+		/// Returns the prizes awarded to the winners of every lottery since genesis
+		fn total_prizes_paid() -> Balance;
+
+		/// Returns the number of draws the account won since genesis, across every lottery
+		fn lifetime_wins(who: AccountId) -> u32;
+	}
+}
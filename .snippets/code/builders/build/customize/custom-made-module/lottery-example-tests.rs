@@ -0,0 +1,843 @@
+//! Tests for the lottery example module
+
+use super::*;
+use crate::mock::*;
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok, assert_storage_noop,
+	dispatch::Pays,
+	pallet_prelude::ValueQuery,
+	traits::{Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade, Randomness, StorageVersion},
+	Blake2_128Concat, PalletId,
+};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::AccountIdConversion, Perbill, Permill};
+
+// Creates a lottery selling tickets at the given cost, returning its id
+fn create_lottery(ticket_cost: Balance) -> LotteryId {
+	let lottery_id = Lottery::get_next_lottery_id();
+	assert_ok!(Lottery::create_lottery(
+		RuntimeOrigin::signed(ALICE),
+		LotteryConfig { ticket_cost, max_participants: 10 },
+		RandomnessSource::OnChain,
+	));
+	lottery_id
+}
+
+// Buys the given number of tickets for each account, paid by the account itself
+fn buy_tickets(lottery_id: LotteryId, entries: &[(AccountId, u32)]) {
+	for (who, quantity) in entries {
+		assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(*who), lottery_id, *quantity));
+	}
+}
+
+// Moves to the end of the ticket sales of the current round, so it can be drawn
+fn close_sales(lottery_id: LotteryId) {
+	System::set_block_number(Lottery::get_round_end(lottery_id).expect("The round has started"));
+}
+
+// Closes the ticket sales of the current round and draws it
+fn draw(lottery_id: LotteryId) {
+	close_sales(lottery_id);
+	assert_ok!(Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id));
+}
+
+// Each of the participants is selected about as often over many seeds, with a
+// number of participants that doesn't divide the range of the random numbers
+#[test]
+fn winner_selection_is_uniform() {
+	const PARTICIPANTS: u32 = 7;
+	const SEEDS: u32 = 70_000;
+	let mut selected = [0u32; PARTICIPANTS as usize];
+	for index in 0..SEEDS {
+		let seed = blake2_256(&index.encode());
+		selected[Lottery::select_winner_index(&seed, PARTICIPANTS) as usize] += 1;
+	}
+	let expected = SEEDS / PARTICIPANTS;
+	for (participant, count) in selected.iter().enumerate() {
+		assert!(
+			count.abs_diff(expected) < expected / 20,
+			"Participant {} selected {} times, expected about {}",
+			participant,
+			count,
+			expected,
+		);
+	}
+}
+
+// With three quarters of the range of a u32 as participants, reducing a random
+// u32 would select the first third of them half of the time. Scaling a random
+// u64 down to the participants selects it a third of the time
+#[test]
+fn winner_selection_has_no_biased_range() {
+	const SEEDS: u32 = 30_000;
+	let participants = 3u32 << 30;
+	let first_third = (0..SEEDS)
+		.filter(|index| {
+			let seed = blake2_256(&index.encode());
+			Lottery::select_winner_index(&seed, participants) < participants / 3
+		})
+		.count() as u32;
+	assert!(
+		first_third.abs_diff(SEEDS / 3) < SEEDS / 50,
+		"First third selected {} times out of {}",
+		first_third,
+		SEEDS,
+	);
+}
+
+#[test]
+fn draw_awards_a_participant() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 3)]);
+		draw(lottery_id);
+
+		let history = Lottery::get_winner_history(lottery_id);
+		assert_eq!(history.len(), 1);
+		assert!([ALICE, BOB].contains(&history[0].winner));
+		assert_eq!(history[0].pot, 40);
+		assert_eq!(Lottery::participant_count(lottery_id), 0);
+		assert_eq!(Lottery::get_current_round(lottery_id), 1);
+	});
+}
+
+// A transfer failing during the draw rolls the whole draw back, leaving the
+// participants and their funds in place so the round can be drawn again
+#[test]
+fn failed_transfer_rolls_back_the_draw() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1)]);
+		close_sales(lottery_id);
+
+		// The fee is below the existential deposit, and can't create the account
+		// of the fee destination, so paying it fails once the ticket funds are
+		// collected into the lottery's account
+		ExistentialDeposit::set(5);
+		assert_storage_noop!(assert!(Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id).is_err()));
+		assert_eq!(Lottery::participant_count(lottery_id), 2);
+		assert_eq!(Lottery::get_reserved_funds(lottery_id, ALICE), 10);
+		assert_eq!(Balances::reserved_balance(BOB), 10);
+		assert_eq!(Balances::free_balance(Lottery::get_pallet_account(lottery_id)), 0);
+		assert!(!Lottery::get_round_drawn(lottery_id));
+
+		// Once the fee can be paid, the same round is drawn
+		ExistentialDeposit::set(1);
+		assert_ok!(Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id));
+		assert_eq!(Lottery::participant_count(lottery_id), 0);
+		assert_eq!(Lottery::get_winner_history(lottery_id)[0].round, 0);
+	});
+}
+
+// Randomness too short to read a number from, as returned by a misconfigured
+// source, is hashed into a usable seed instead of failing the selection. The
+// draw hashes short randomness the same way before selecting the winners
+#[test]
+fn short_seed_is_hashed() {
+	let seed = [0x12u8, 0x34];
+	for participants in [1, 2, 7, 100] {
+		let index = Lottery::select_winner_index(&seed, participants);
+		assert!(index < participants);
+		assert_eq!(index, Lottery::select_winner_index(&blake2_256(&seed), participants));
+	}
+	assert_eq!(
+		Lottery::select_winner_index(&[], 7),
+		Lottery::select_winner_index(&blake2_256(&[]), 7),
+	);
+}
+
+// The lottery's own account can't enter its lottery, whether bought for,
+// buying with its own funds or added by the admin
+#[test]
+fn lottery_account_cannot_participate() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		let lottery_account = Lottery::get_pallet_account(lottery_id);
+		assert_noop!(
+			Lottery::buy_ticket_for(RuntimeOrigin::signed(ALICE), lottery_id, lottery_account, 1),
+			Error::<Test>::InvalidParticipant
+		);
+		Balances::make_free_balance_be(&lottery_account, 100);
+		assert_noop!(
+			Lottery::buy_ticket(RuntimeOrigin::signed(lottery_account), lottery_id, 1),
+			Error::<Test>::InvalidParticipant
+		);
+		assert_noop!(
+			Lottery::force_add_participant(RuntimeOrigin::root(), lottery_id, lottery_account),
+			Error::<Test>::InvalidParticipant
+		);
+		assert_eq!(Lottery::participant_count(lottery_id), 0);
+	});
+}
+
+// The lottery's account is kept alive by the payouts while it holds a jackpot
+// carried over, and may be reaped once a payout leaves nothing owed
+#[test]
+fn payout_keeps_the_carryover_alive() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		let lottery_account = Lottery::get_pallet_account(lottery_id);
+
+		// The prize cap carries 5 over to the next round, which stay in the
+		// lottery's account once the prize is claimed
+		LotteryMaxPrize::set(Some(15));
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1)]);
+		draw(lottery_id);
+		let winner = Lottery::get_winner_history(lottery_id)[0].winner;
+		assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(winner), lottery_id));
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 5);
+		assert_eq!(Balances::free_balance(lottery_account), 5);
+		assert!(System::account_exists(&lottery_account));
+
+		// Without a cap, the next prize takes the carryover along, and its
+		// payout empties the account
+		LotteryMaxPrize::set(None);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1)]);
+		draw(lottery_id);
+		let winner = Lottery::get_winner_history(lottery_id)[1].winner;
+		assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(winner), lottery_id));
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 0);
+		assert!(!System::account_exists(&lottery_account));
+	});
+}
+
+// An empty participants list left in storage is drawn as no participants at
+// all, rolling the round over
+#[cfg(not(feature = "indexed-participants"))]
+#[test]
+fn empty_participants_list_rolls_over() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		Participants::<Test>::insert(lottery_id, frame_support::BoundedVec::default());
+		assert_ok!(Lottery::award_prize(RuntimeOrigin::root(), lottery_id));
+		System::assert_has_event(Event::<Test>::ThereAreNoParticipants { lottery_id }.into());
+		assert!(Lottery::get_participants(lottery_id).is_none());
+		assert_eq!(Lottery::get_current_round(lottery_id), 1);
+		assert!(Lottery::get_winner_history(lottery_id).is_empty());
+	});
+}
+
+// A ticket cost too large to add up fails the purchase instead of overflowing
+#[test]
+fn overflowing_tickets_cost_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(Balance::MAX / 2 + 1);
+		assert_noop!(
+			Lottery::buy_ticket(RuntimeOrigin::signed(ALICE), lottery_id, 2),
+			Error::<Test>::ArithmeticOverflow
+		);
+	});
+}
+
+// A pot close to the largest balance is split without overflowing, with every
+// part of it accounted for
+#[test]
+fn pot_near_the_largest_balance_is_split() {
+	let endowment = Balance::MAX / 2 - 1_000;
+	let ticket_cost = endowment - 1_000;
+	ExtBuilder::default()
+		.balances(vec![(ALICE, endowment), (BOB, endowment)])
+		.build()
+		.execute_with(|| {
+			let lottery_id = create_lottery(ticket_cost);
+			buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1)]);
+			draw(lottery_id);
+
+			let record = Lottery::get_winner_history(lottery_id)[0].clone();
+			assert_eq!(record.pot, 2 * ticket_cost);
+			assert_eq!(record.fee, LotteryFeePercent::get().mul_floor(record.pot));
+			assert_eq!(record.prize + record.fee, record.pot);
+			assert_eq!(Balances::free_balance(TREASURY), record.fee);
+			assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(record.winner), lottery_id));
+			assert_eq!(Balances::free_balance(record.winner), 1_000 + record.prize);
+		});
+}
+
+// The fee, the burn and the prizes of each round recorded in the history, along
+// with the funds left in the lottery's account, add up to the pot the round was
+// drawn with
+#[test]
+fn history_reconciles_with_the_pot() {
+	new_test_ext().execute_with(|| {
+		LotteryBurnPercent::set(Permill::from_percent(10));
+		LotterySeedNextRoundPercent::set(Permill::from_percent(20));
+		LotteryWinnersPerRound::set(2);
+		let lottery_id = create_lottery(10);
+		let lottery_account = Lottery::get_pallet_account(lottery_id);
+
+		for round in 0..2 {
+			buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2), (CHARLIE, 3)]);
+			let carried = Lottery::get_jackpot_carryover(lottery_id);
+			draw(lottery_id);
+
+			let records: Vec<_> = Lottery::get_winner_history(lottery_id)
+				.into_iter()
+				.filter(|record| record.round == round)
+				.collect();
+			assert_eq!(records.len(), 2);
+			let (fee, burned, pot) = (records[0].fee, records[0].burned, records[0].pot);
+			let prizes: Balance = records.iter().map(|record| record.prize).sum();
+			let residual = Lottery::get_jackpot_carryover(lottery_id);
+			assert_eq!(pot, 60 + carried);
+			assert_eq!(fee + burned + prizes + residual, pot);
+			assert_eq!(Balances::free_balance(lottery_account), pot - fee - burned);
+
+			// Once the prizes are claimed, only the residual is left
+			for record in records {
+				assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(record.winner), lottery_id));
+			}
+			assert_eq!(Balances::free_balance(lottery_account), residual);
+		}
+	});
+}
+
+// The winners history kept before version 2 is rewritten as records, keeping
+// every winner, with the fields that were not kept set to zero
+#[test]
+fn migration_to_v2_keeps_the_winners() {
+	new_test_ext().execute_with(|| {
+		#[frame_support::storage_alias]
+		type WinnerHistory = StorageMap<
+			Lottery,
+			Blake2_128Concat,
+			LotteryId,
+			Vec<(u32, AccountId, Balance)>,
+			ValueQuery,
+		>;
+		WinnerHistory::insert(0, vec![(0, ALICE, 19), (1, BOB, 38)]);
+		WinnerHistory::insert(1, vec![(0, CHARLIE, 5)]);
+		StorageVersion::new(1).put::<Lottery>();
+
+		#[cfg(feature = "try-runtime")]
+		let state = migrations::v2::MigrateToV2::<Test>::pre_upgrade().unwrap();
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		#[cfg(feature = "try-runtime")]
+		assert_ok!(migrations::v2::MigrateToV2::<Test>::post_upgrade(state));
+
+		assert_eq!(Lottery::on_chain_storage_version(), 2);
+		let history = Lottery::get_winner_history(0);
+		assert_eq!(
+			history.iter().map(|record| (record.round, record.winner, record.prize)).collect::<Vec<_>>(),
+			vec![(0, ALICE, 19), (1, BOB, 38)],
+		);
+		assert!(history.iter().all(|record| {
+			record.fee == 0 && record.burned == 0 && record.pot == 0 && record.participant_count == 0
+		}));
+		assert_eq!(Lottery::get_winner_history(1).len(), 1);
+	});
+}
+
+// The remainder of splitting the prize between the winners lands in the
+// configured destination. The 29 won by three winners leave 2 of dust
+#[test]
+fn dust_goes_to_the_configured_destination() {
+	for destination in [
+		DustDestination::Winner,
+		DustDestination::Treasury,
+		DustDestination::Carryover,
+		DustDestination::Burn,
+	] {
+		new_test_ext().execute_with(|| {
+			LotteryDustDestination::set(destination);
+			LotteryWinnersPerRound::set(3);
+			let lottery_id = create_lottery(10);
+			buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1), (CHARLIE, 1)]);
+			let issuance = Balances::total_issuance();
+			draw(lottery_id);
+
+			let prizes: Vec<_> = Lottery::get_winner_history(lottery_id)
+				.iter()
+				.map(|record| record.prize)
+				.collect();
+			let fee = Balances::free_balance(TREASURY);
+			let carryover = Lottery::get_jackpot_carryover(lottery_id);
+			let burned = issuance - Balances::total_issuance();
+			let (expected_prizes, expected_dust) = match destination {
+				DustDestination::Winner => (vec![11, 9, 9], (1, 0, 0)),
+				DustDestination::Treasury => (vec![9, 9, 9], (3, 0, 0)),
+				DustDestination::Carryover => (vec![9, 9, 9], (1, 2, 0)),
+				DustDestination::Burn => (vec![9, 9, 9], (1, 0, 2)),
+			};
+			assert_eq!(prizes, expected_prizes, "Prizes with the dust to {:?}", destination);
+			assert_eq!((fee, carryover, burned), expected_dust, "Dust to {:?}", destination);
+		});
+	}
+}
+
+// A duplicate purchase fails under the rejecting policy
+#[test]
+fn duplicate_purchase_is_rejected() {
+	new_test_ext().execute_with(|| {
+		LotteryDuplicatePurchase::set(DuplicatePurchase::Reject);
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1)]);
+		assert_noop!(
+			Lottery::buy_ticket(RuntimeOrigin::signed(ALICE), lottery_id, 2),
+			Error::<Test>::AccountAlreadyParticipating
+		);
+	});
+}
+
+// A duplicate purchase succeeds without buying anything under the idempotent
+// policy, and is free
+#[test]
+fn duplicate_purchase_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		LotteryDuplicatePurchase::set(DuplicatePurchase::Idempotent);
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1)]);
+		assert_eq!(
+			Lottery::buy_ticket(RuntimeOrigin::signed(ALICE), lottery_id, 2),
+			Ok(Pays::No.into())
+		);
+		assert_eq!(Lottery::tickets_of(lottery_id, &ALICE), 1);
+		assert_eq!(Lottery::get_reserved_funds(lottery_id, ALICE), 10);
+		assert_eq!(Balances::free_balance(ALICE), 990);
+	});
+}
+
+// A duplicate purchase adds the tickets to the ones held under the policy
+// allowing multiple purchases
+#[test]
+fn duplicate_purchase_adds_tickets() {
+	new_test_ext().execute_with(|| {
+		LotteryDuplicatePurchase::set(DuplicatePurchase::AllowMultiple);
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (ALICE, 2)]);
+		assert_eq!(Lottery::tickets_of(lottery_id, &ALICE), 3);
+		assert_eq!(Lottery::get_reserved_funds(lottery_id, ALICE), 30);
+		assert_eq!(Lottery::participant_count(lottery_id), 1);
+	});
+}
+
+// A donation is carried over to the next prize, so the emergency withdrawal
+// can't take it, while funds sent to the lottery's account by hand can be
+#[test]
+fn donation_cannot_be_withdrawn() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		let lottery_account = Lottery::get_pallet_account(lottery_id);
+		assert_ok!(Lottery::donate(RuntimeOrigin::signed(ALICE), lottery_id, 50));
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 50);
+		assert_noop!(
+			Lottery::emergency_withdraw(RuntimeOrigin::root(), lottery_id, DAVE, 50),
+			Error::<Test>::NoStrayFunds
+		);
+
+		// Only the 20 sent by hand are stray
+		Balances::make_free_balance_be(&lottery_account, 70);
+		assert_ok!(Lottery::emergency_withdraw(RuntimeOrigin::root(), lottery_id, DAVE, 100));
+		assert_eq!(Balances::free_balance(DAVE), 1_020);
+		assert_eq!(Balances::free_balance(lottery_account), 50);
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 50);
+	});
+}
+
+// With more winners per round than participants, every participant wins an
+// equal share of the prize, whatever the tickets held
+#[test]
+fn every_participant_wins_when_outnumbered_by_winners() {
+	new_test_ext().execute_with(|| {
+		LotteryWinnersPerRound::set(5);
+		let lottery_id = create_lottery(20);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2), (CHARLIE, 3)]);
+		draw(lottery_id);
+
+		// The 120 of tickets, less the fee of 6, are split in three
+		let mut winners: Vec<_> = Lottery::get_winner_history(lottery_id)
+			.into_iter()
+			.map(|record| (record.winner, record.prize))
+			.collect();
+		winners.sort();
+		assert_eq!(winners, vec![(ALICE, 38), (BOB, 38), (CHARLIE, 38)]);
+		for who in [ALICE, BOB, CHARLIE] {
+			assert_eq!(Lottery::get_unclaimed_prize(lottery_id, who).map(|(owed, _)| owed), Some(38));
+		}
+	});
+}
+
+// Once the funds held by the lottery reach the escrow cap, both the purchases
+// and the donations that would exceed it are rejected
+#[test]
+fn escrow_cap_rejects_the_next_contribution() {
+	new_test_ext().execute_with(|| {
+		LotteryMaxEscrow::set(Some(30));
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2)]);
+		assert_eq!(Lottery::current_pot(lottery_id), 30);
+		assert_noop!(
+			Lottery::buy_ticket(RuntimeOrigin::signed(CHARLIE), lottery_id, 1),
+			Error::<Test>::EscrowCapExceeded
+		);
+		assert_noop!(
+			Lottery::donate(RuntimeOrigin::signed(CHARLIE), lottery_id, 1),
+			Error::<Test>::EscrowCapExceeded
+		);
+	});
+}
+
+// A purchase for someone else whose transfer fails after the balance check,
+// as it would reap the payer, registers no participant
+#[test]
+fn failed_transfer_registers_no_participant() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		Balances::make_free_balance_be(&DAVE, 10);
+		assert_storage_noop!(assert!(
+			Lottery::buy_ticket_for(RuntimeOrigin::signed(DAVE), lottery_id, BOB, 1).is_err()
+		));
+		assert_eq!(Lottery::participant_count(lottery_id), 0);
+		assert_eq!(Lottery::tickets_of(lottery_id, &BOB), 0);
+		assert_eq!(Lottery::get_reserved_funds(lottery_id, BOB), 0);
+		assert_eq!(Lottery::get_tickets_issued(lottery_id), 0);
+		assert_eq!(Balances::free_balance(DAVE), 10);
+	});
+}
+
+// Draws a round entered by the accounts in the given order, and returns the
+// hash of its participants
+fn participants_hash_of(entries: &[(AccountId, u32)]) -> [u8; 32] {
+	let accounts = entries.iter().map(|(who, _)| (*who, 1_000)).collect();
+	ExtBuilder::default().balances(accounts).build().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, entries);
+		draw(lottery_id);
+		Lottery::round_participants_hash(lottery_id, 0).expect("The round is in the history")
+	})
+}
+
+// The hash of the participants matches the one computed off-chain from the
+// documented encoding, whatever the order the participants entered in. The
+// account 256 is encoded with a leading zero byte, so it sorts first
+#[test]
+fn participants_hash_matches_the_reference() {
+	const EVE: AccountId = 256;
+	let mut encoded = Vec::new();
+	for who in [EVE, ALICE, BOB] {
+		encoded.extend(who.to_le_bytes());
+	}
+	let reference = blake2_256(&encoded);
+
+	assert_eq!(participants_hash_of(&[(ALICE, 1), (BOB, 2), (EVE, 3)]), reference);
+	assert_eq!(participants_hash_of(&[(EVE, 3), (BOB, 2), (ALICE, 1)]), reference);
+	assert_eq!(participants_hash_of(&[(BOB, 2), (ALICE, 1), (EVE, 3)]), reference);
+}
+
+// A ticket cost below the minimum is rejected, both when creating the lottery
+// and when changing its cost, while a cost right at the minimum is accepted
+#[test]
+fn ticket_cost_below_the_minimum_is_rejected() {
+	new_test_ext().execute_with(|| {
+		LotteryMinTicketCost::set(5);
+		assert_noop!(
+			Lottery::create_lottery(
+				RuntimeOrigin::signed(ALICE),
+				LotteryConfig { ticket_cost: 4, max_participants: 10 },
+				RandomnessSource::OnChain,
+			),
+			Error::<Test>::TicketCostTooLow
+		);
+		let lottery_id = create_lottery(5);
+
+		assert_ok!(Lottery::set_ticket_cost(RuntimeOrigin::root(), lottery_id, 8));
+		assert_noop!(
+			Lottery::set_ticket_cost(RuntimeOrigin::root(), lottery_id, 4),
+			Error::<Test>::TicketCostTooLow
+		);
+		assert_ok!(Lottery::set_ticket_cost(RuntimeOrigin::root(), lottery_id, 5));
+		assert_eq!(Lottery::get_lottery(lottery_id).map(|lottery| lottery.ticket_cost), Some(5));
+	});
+}
+
+// A scratch is only rolled on a block after it's bought, on randomness that
+// wasn't known yet, and a winning one is owed the prize out of the pot
+#[test]
+fn scratch_is_rolled_on_a_later_block() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		assert_ok!(Lottery::donate(RuntimeOrigin::signed(BOB), lottery_id, 100));
+		LotteryScratchOdds::set(Perbill::one());
+
+		assert_ok!(Lottery::scratch(RuntimeOrigin::signed(ALICE), lottery_id));
+		assert_eq!(Lottery::get_pending_scratches(2), vec![(lottery_id, ALICE)]);
+		assert_eq!(Lottery::get_unclaimed_prize(lottery_id, ALICE), None);
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 110);
+
+		System::set_block_number(2);
+		Lottery::on_initialize(2);
+		assert!(Lottery::get_pending_scratches(2).is_empty());
+		assert_eq!(Lottery::get_unclaimed_prize(lottery_id, ALICE), Some((50, 2)));
+		assert_eq!(Lottery::get_jackpot_carryover(lottery_id), 60);
+		System::assert_has_event(
+			Event::<Test>::ScratchResult { lottery_id, who: ALICE, won: true, amount: 50 }.into()
+		);
+	});
+}
+
+// Every winner asked for is drawn however skewed the tickets held, instead of
+// the draws running out on the tickets of the largest holder
+#[test]
+fn skewed_holdings_draw_every_winner() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, 10_000), (BOB, 1_000), (CHARLIE, 1_000), (DAVE, 1_000)])
+		.build()
+		.execute_with(|| {
+			LotteryWinnersPerRound::set(2);
+			let lottery_id = create_lottery(1);
+
+			for round in 0..5 {
+				buy_tickets(lottery_id, &[(ALICE, 1_000), (BOB, 1), (CHARLIE, 1)]);
+				draw(lottery_id);
+
+				let winners: Vec<_> = Lottery::get_winner_history(lottery_id)
+					.into_iter()
+					.filter(|record| record.round == round)
+					.map(|record| record.winner)
+					.collect();
+				assert_eq!(winners.len(), 2);
+				assert_ne!(winners[0], winners[1]);
+			}
+		});
+}
+
+// The bonus ticket of a referral counts as sold, so refunding it when the
+// referrer leaves doesn't take it out of the tickets sold by other lotteries
+#[test]
+fn referral_bonus_counts_as_sold() {
+	new_test_ext().execute_with(|| {
+		let first = create_lottery(10);
+		let second = create_lottery(10);
+		buy_tickets(first, &[(CHARLIE, 2)]);
+		buy_tickets(second, &[(ALICE, 1)]);
+		assert_ok!(Lottery::buy_ticket_with_referral(RuntimeOrigin::signed(BOB), second, 1, ALICE));
+		assert_eq!(Lottery::tickets_of(second, &ALICE), 2);
+		assert_eq!(Lottery::get_total_tickets_sold(), 5);
+
+		assert_ok!(Lottery::leave_lottery(RuntimeOrigin::signed(ALICE), second));
+		assert_ok!(Lottery::leave_lottery(RuntimeOrigin::signed(ALICE), second));
+		assert_eq!(Lottery::tickets_of(second, &ALICE), 0);
+		assert_eq!(Lottery::get_total_tickets_sold(), 3);
+	});
+}
+
+// Modules configured with different ids hold the funds of the same lottery in
+// different accounts, each distinct from the accounts of their other lotteries
+#[test]
+fn pallet_id_sets_the_escrow_accounts() {
+	new_test_ext().execute_with(|| {
+		let first = Lottery::get_pallet_account(0);
+		assert_ne!(first, Lottery::get_pallet_account(1));
+
+		LotteryPalletId::set(PalletId(*b"loex0000"));
+		let second = Lottery::get_pallet_account(0);
+		assert_ne!(first, second);
+		assert_ne!(second, Lottery::get_pallet_account(1));
+		let expected: AccountId = PalletId(*b"loex0000").into_sub_account_truncating(0u32);
+		assert_eq!(second, expected);
+	});
+}
+
+// A proxy restricted to buying tickets buys them for the proxied account,
+// which pays for them and is registered as the participant, while the other
+// calls made through it are filtered out before reaching the module
+#[test]
+fn proxied_purchase_registers_the_proxied_account() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(ALICE), BOB, ProxyType::BuyTicket, 0));
+
+		let buy = RuntimeCall::Lottery(Call::<Test>::buy_ticket { lottery_id, quantity: 2 });
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(BOB), ALICE, None, Box::new(buy)));
+		assert_eq!(Lottery::tickets_of(lottery_id, &ALICE), 2);
+		assert_eq!(Lottery::tickets_of(lottery_id, &BOB), 0);
+		assert_eq!(Lottery::get_reserved_funds(lottery_id, &ALICE), 20);
+
+		buy_tickets(lottery_id, &[(CHARLIE, 1)]);
+		close_sales(lottery_id);
+		for call in [Call::<Test>::award_prize { lottery_id }, Call::<Test>::draw { lottery_id }] {
+			let call = RuntimeCall::Lottery(call);
+			assert_ok!(Proxy::proxy(RuntimeOrigin::signed(BOB), ALICE, None, Box::new(call)));
+			System::assert_last_event(
+				pallet_proxy::Event::ProxyExecuted {
+					result: Err(frame_system::Error::<Test>::CallFiltered.into()),
+				}
+				.into(),
+			);
+		}
+		assert_eq!(Lottery::get_current_round(lottery_id), 0);
+	});
+}
+
+// A prize below the existential deposit, won by an account left with no balance,
+// stays owed to the winner instead of being lost, and the lottery's account
+// keeps holding it along with the funds carried over
+#[test]
+fn prize_below_the_existential_deposit_is_kept_owed() {
+	const EVE: AccountId = 5;
+	ExistentialDeposit::set(5);
+	ExtBuilder::default()
+		.balances(vec![(ALICE, 1_000), (BOB, 1_000), (DAVE, 1_000), (EVE, 5)])
+		.build()
+		.execute_with(|| {
+			LotteryWinnersPerRound::set(3);
+			LotteryMaxPrize::set(Some(12));
+			let lottery_id = create_lottery(5);
+			let lottery_account = Lottery::get_pallet_account(lottery_id);
+			assert_ok!(Lottery::donate(RuntimeOrigin::signed(ALICE), lottery_id, 10));
+			let issuance = Balances::total_issuance();
+
+			// Every participant wins a third of the capped prize, and the winner
+			// spent its whole balance on its ticket
+			buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 1), (EVE, 1)]);
+			draw(lottery_id);
+			assert_eq!(Balances::total_balance(&EVE), 0);
+			assert_eq!(Lottery::get_unclaimed_prize(lottery_id, EVE).map(|(amount, _)| amount), Some(4));
+
+			assert_noop!(
+				Lottery::claim_prize(RuntimeOrigin::signed(EVE), lottery_id),
+				Error::<Test>::PrizeBelowExistentialDeposit
+			);
+			assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(ALICE), lottery_id));
+			assert_ok!(Lottery::claim_prize(RuntimeOrigin::signed(BOB), lottery_id));
+
+			assert!(System::account_exists(&lottery_account));
+			assert_eq!(Lottery::get_unclaimed_prize(lottery_id, EVE).map(|(amount, _)| amount), Some(4));
+			assert_eq!(Lottery::get_total_unclaimed(lottery_id), 4);
+			assert_eq!(
+				Balances::free_balance(lottery_account),
+				Lottery::get_jackpot_carryover(lottery_id) + Lottery::get_total_unclaimed(lottery_id)
+			);
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+}
+
+// The subject the next draw asks the randomness for, over the given number of
+// participants
+fn draw_subject(participants: u32) -> Vec<u8> {
+	(Lottery::get_nonce().encode(), System::parent_hash(), participants).encode()
+}
+
+// The first ticket number a draw seeded with the randomness picks
+fn first_winning_number(random: &[u8], issued: u32) -> u32 {
+	Lottery::select_winner_indices(random, issued, 1).next().expect("Tickets were issued")
+}
+
+// The numbers drawn so far, along with the block of the randomness drawing them
+fn drawn_numbers() -> Vec<(u32, u64)> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Lottery(Event::DrawComputed { winning_number, randomness_block, .. }) => {
+				Some((winning_number, randomness_block))
+			},
+			_ => None,
+		})
+		.collect()
+}
+
+// A lottery drawn on the randomness module alone picks the ticket pointed at
+// by the randomness of the draw's subject
+#[cfg(not(feature = "vrf-randomness"))]
+#[test]
+fn on_chain_draw_uses_the_randomness_module() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2)]);
+		close_sales(lottery_id);
+
+		let (random, block) = TestRandomness::random(&draw_subject(2));
+		assert_ok!(Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id));
+		assert_eq!(drawn_numbers(), vec![(first_winning_number(random.as_ref(), 3), block)]);
+	});
+}
+
+// A commit-reveal lottery is only drawn by revealing the committed seed, which
+// is mixed into the randomness of the draw's subject
+#[cfg(not(feature = "vrf-randomness"))]
+#[test]
+fn commit_reveal_draw_mixes_in_the_revealed_seed() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = Lottery::get_next_lottery_id();
+		assert_ok!(Lottery::create_lottery(
+			RuntimeOrigin::signed(ALICE),
+			LotteryConfig { ticket_cost: 10, max_participants: 10 },
+			RandomnessSource::CommitReveal,
+		));
+		let seed = [7u8; 32];
+		assert_ok!(Lottery::commit_seed(RuntimeOrigin::root(), lottery_id, blake2_256(&seed)));
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2)]);
+		close_sales(lottery_id);
+
+		assert_noop!(
+			Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id),
+			Error::<Test>::SeedRevealRequired
+		);
+		assert_noop!(
+			Lottery::reveal_and_draw(RuntimeOrigin::root(), lottery_id, [8u8; 32]),
+			Error::<Test>::InvalidSeed
+		);
+
+		let (random, block) = TestRandomness::random(&draw_subject(2));
+		let mixed: Vec<u8> = random.as_ref().iter().zip(seed.iter()).map(|(byte, seed)| byte ^ seed).collect();
+		assert_ok!(Lottery::reveal_and_draw(RuntimeOrigin::root(), lottery_id, seed));
+		assert_eq!(drawn_numbers(), vec![(first_winning_number(&mixed, 3), block)]);
+		assert_eq!(Lottery::get_current_round(lottery_id), 1);
+	});
+}
+
+// With the VRF randomness available, the draw is seeded with it instead of
+// the randomness module's, and records the block of the VRF output
+#[cfg(feature = "vrf-randomness")]
+#[test]
+fn vrf_draw_uses_the_validator_randomness() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		buy_tickets(lottery_id, &[(ALICE, 1), (BOB, 2)]);
+		close_sales(lottery_id);
+
+		let (random, block) = TestVrfRandomness::random(&draw_subject(2));
+		let random = random.expect("The VRF randomness is always available");
+		assert_ok!(Lottery::draw(RuntimeOrigin::signed(DAVE), lottery_id));
+		assert_eq!(drawn_numbers(), vec![(first_winning_number(random.as_ref(), 3), block)]);
+
+		// The randomness module's randomness is always from the genesis block
+		assert_ne!(block, 0);
+	});
+}
+
+// Root recovers the funds stuck in the lottery's account while the lotteries
+// are disabled, without touching the funds carried over
+#[test]
+fn emergency_withdraw_works_while_disabled() {
+	new_test_ext().execute_with(|| {
+		let lottery_id = create_lottery(10);
+		let lottery_account = Lottery::get_pallet_account(lottery_id);
+		assert_ok!(Lottery::donate(RuntimeOrigin::signed(ALICE), lottery_id, 20));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(BOB), lottery_account, 30));
+		assert_ok!(Lottery::disable_lottery(RuntimeOrigin::root()));
+
+		assert_ok!(Lottery::emergency_withdraw(RuntimeOrigin::root(), lottery_id, DAVE, 100));
+		assert_eq!(Balances::free_balance(DAVE), 1_030);
+		assert_eq!(Balances::free_balance(lottery_account), 20);
+		assert_noop!(
+			Lottery::emergency_withdraw(RuntimeOrigin::root(), lottery_id, DAVE, 1),
+			Error::<Test>::NoStrayFunds
+		);
+	});
+}
+
+// Seeds that only differ past their first 8 bytes select the same index, and
+// the index grows with the number read from them
+#[test]
+fn winner_selection_scales_the_seed() {
+	let mut seed = [0u8; 32];
+	assert_eq!(Lottery::select_winner_index(&seed, 7), 0);
+	seed[8..].fill(0xff);
+	assert_eq!(Lottery::select_winner_index(&seed, 7), 0);
+	seed[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+	assert_eq!(Lottery::select_winner_index(&seed, 7), 6);
+	seed[..8].copy_from_slice(&(1u64 << 63).to_le_bytes());
+	assert_eq!(Lottery::select_winner_index(&seed, 7), 3);
+	assert_eq!(Lottery::select_winner_index(&seed, u32::MAX), u32::MAX / 2);
+}
@@ -0,0 +1,1110 @@
+//! Weights for the lottery example module
+//!
+//! These weights are meant to be regenerated for the target hardware 
+//! by running the module benchmarks with the benchmark CLI:
+//!
+//! ./target/release/container-chain-template-frontier-node benchmark pallet
+//! --chain=dev
+//! --steps=50
+//! --repeat=20
+//! --pallet=pallet_lottery_example
+//! --extrinsic=*
+//! --wasm-execution=compiled
+//! --output=weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for the lottery example module.
+pub trait WeightInfo {
+	fn create_lottery() -> Weight;
+	fn buy_ticket(p: u32, ) -> Weight;
+	fn award_prize(p: u32, ) -> Weight;
+	fn set_ticket_cost() -> Weight;
+	fn cancel_lottery(p: u32, ) -> Weight;
+	fn leave_lottery(p: u32, ) -> Weight;
+	fn draw(p: u32, ) -> Weight;
+	fn add_to_allowlist() -> Weight;
+	fn remove_from_allowlist() -> Weight;
+	fn commit_seed() -> Weight;
+	fn reveal_and_draw(p: u32, ) -> Weight;
+	fn donate() -> Weight;
+	fn claim_prize() -> Weight;
+	fn sweep_expired(n: u32, ) -> Weight;
+	fn buy_ticket_for(p: u32, ) -> Weight;
+	fn buy_tickets(n: u32, ) -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn on_idle_payout() -> Weight;
+	fn draw_unsigned(p: u32, ) -> Weight;
+	fn buy_ticket_with_referral(p: u32, ) -> Weight;
+	fn force_add_participant(p: u32, ) -> Weight;
+	fn force_remove_participant(p: u32, ) -> Weight;
+	fn reduce_tickets(p: u32, t: u32, ) -> Weight;
+	fn scratch() -> Weight;
+	fn set_max_participants() -> Weight;
+	fn reset_nonce() -> Weight;
+	fn add_fee_exempt() -> Weight;
+	fn remove_fee_exempt() -> Weight;
+	fn emergency_withdraw() -> Weight;
+	fn buy_ticket_with_data(p: u32, ) -> Weight;
+	fn enable_lottery() -> Weight;
+	fn disable_lottery() -> Weight;
+	fn cancel_lottery_paged(p: u32, ) -> Weight;
+	fn subscribe() -> Weight;
+	fn unsubscribe() -> Weight;
+	fn add_to_allowlist_batch(n: u32, ) -> Weight;
+	fn remove_from_allowlist_batch(n: u32, ) -> Weight;
+	fn force_settle_stale_round(p: u32, ) -> Weight;
+	fn set_prize_source() -> Weight;
+	fn settle_scratches(n: u32, ) -> Weight;
+}
+
+/// Weights for the lottery example module using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery NextLotteryId (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:0 w:1)
+	/// Storage: Lottery LotteryRandomness (r:0 w:1)
+	/// Storage: Lottery CreationDeposits (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn create_lottery() -> Weight {
+		Weight::from_parts(38_120_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket(p: u32, ) -> Weight {
+		Weight::from_parts(40_622_000, 3_593)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn award_prize(p: u32, ) -> Weight {
+		Weight::from_parts(66_570_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:1)
+	fn set_ticket_cost() -> Weight {
+		Weight::from_parts(11_980_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn cancel_lottery(p: u32, ) -> Weight {
+		Weight::from_parts(29_840_000, 3_593)
+			.saturating_add(Weight::from_parts(27_420_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn leave_lottery(p: u32, ) -> Weight {
+		Weight::from_parts(38_840_000, 3_593)
+			.saturating_add(Weight::from_parts(101_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:4 w:4)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn draw(p: u32, ) -> Weight {
+		Weight::from_parts(87_910_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:1 w:1)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(14_870_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:1 w:1)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(14_610_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery SeedCommitments (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	fn commit_seed() -> Weight {
+		Weight::from_parts(14_980_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery SeedCommitments (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery RoundDrawn (r:0 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: Lottery JackpotCarryover (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn reveal_and_draw(p: u32, ) -> Weight {
+		Weight::from_parts(77_450_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	fn donate() -> Weight {
+		Weight::from_parts(35_250_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim_prize() -> Weight {
+		Weight::from_parts(37_730_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	/// The range of component `n` is `[0, 500]`.
+	fn sweep_expired(n: u32, ) -> Weight {
+		Weight::from_parts(15_590_000, 3_513)
+			.saturating_add(Weight::from_parts(14_260_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_531).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_for(p: u32, ) -> Weight {
+		Weight::from_parts(54_840_000, 6_196)
+			.saturating_add(Weight::from_parts(97_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `n` is `[1, 50]`.
+	fn buy_tickets(n: u32, ) -> Weight {
+		Weight::from_parts(32_520_000, 3_593)
+			.saturating_add(Weight::from_parts(44_850_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_206).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Paused (r:0 w:1)
+	fn pause() -> Weight {
+		Weight::from_parts(11_040_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Paused (r:0 w:1)
+	fn unpause() -> Weight {
+		Weight::from_parts(10_960_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery PendingPayouts (r:2 w:1)
+	/// Storage: Lottery PayoutTotals (r:1 w:1)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn on_idle_payout() -> Weight {
+		Weight::from_parts(44_870_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn draw_unsigned(p: u32, ) -> Weight {
+		Weight::from_parts(68_440_000, 6_196)
+			.saturating_add(Weight::from_parts(27_480_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Referrals (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_with_referral(p: u32, ) -> Weight {
+		Weight::from_parts(51_980_000, 3_593)
+			.saturating_add(Weight::from_parts(181_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 96).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery TicketsIssued (r:1 w:1)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn force_add_participant(p: u32, ) -> Weight {
+		Weight::from_parts(26_420_000, 3_593)
+			.saturating_add(Weight::from_parts(91_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `p` is `[1, 500]`.
+	fn force_remove_participant(p: u32, ) -> Weight {
+		Weight::from_parts(43_370_000, 6_196)
+			.saturating_add(Weight::from_parts(102_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:10)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	/// The range of component `t` is `[1, 10]`.
+	fn reduce_tickets(p: u32, t: u32, ) -> Weight {
+		Weight::from_parts(40_230_000, 3_593)
+			.saturating_add(Weight::from_parts(101_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(2_910_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Paused (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:0)
+	/// Storage: Lottery PendingScratches (r:1 w:1)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn scratch() -> Weight {
+		Weight::from_parts(39_870_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:1)
+	fn set_max_participants() -> Weight {
+		Weight::from_parts(13_350_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	fn reset_nonce() -> Weight {
+		Weight::from_parts(8_230_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery FeeExempt (r:0 w:1)
+	fn add_fee_exempt() -> Weight {
+		Weight::from_parts(12_190_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery FeeExempt (r:0 w:1)
+	fn remove_fee_exempt() -> Weight {
+		Weight::from_parts(12_080_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery JackpotCarryover (r:1 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn emergency_withdraw() -> Weight {
+		Weight::from_parts(48_170_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery TicketMemos (r:0 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_with_data(p: u32, ) -> Weight {
+		Weight::from_parts(43_317_000, 3_593)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:0 w:1)
+	fn enable_lottery() -> Weight {
+		Weight::from_parts(8_710_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:0 w:1)
+	fn disable_lottery() -> Weight {
+		Weight::from_parts(8_760_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery CancellationProgress (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn cancel_lottery_paged(p: u32, ) -> Weight {
+		Weight::from_parts(32_890_000, 3_593)
+			.saturating_add(Weight::from_parts(27_420_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Subscriptions (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn subscribe() -> Weight {
+		Weight::from_parts(31_240_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Subscriptions (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn unsubscribe() -> Weight {
+		Weight::from_parts(27_860_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:50 w:50)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	/// The range of component `n` is `[1, 50]`.
+	fn add_to_allowlist_batch(n: u32, ) -> Weight {
+		Weight::from_parts(13_420_000, 1_489)
+			.saturating_add(Weight::from_parts(6_310_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_475).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:50 w:50)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	/// The range of component `n` is `[1, 50]`.
+	fn remove_from_allowlist_batch(n: u32, ) -> Weight {
+		Weight::from_parts(13_180_000, 1_489)
+			.saturating_add(Weight::from_parts(6_540_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_475).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn force_settle_stale_round(p: u32, ) -> Weight {
+		Weight::from_parts(71_240_000, 6_196)
+			.saturating_add(Weight::from_parts(27_560_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery RoundPrizeSource (r:0 w:1)
+	fn set_prize_source() -> Weight {
+		Weight::from_parts(15_380_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery PendingScratches (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: System Account (r:50 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:50 w:50)
+	/// Storage: Lottery JackpotCarryover (r:50 w:50)
+	/// Storage: Lottery UnclaimedPrizes (r:50 w:50)
+	/// Storage: Lottery TotalPrizesPaid (r:1 w:1)
+	/// The range of component `n` is `[0, 50]`.
+	fn settle_scratches(n: u32, ) -> Weight {
+		Weight::from_parts(6_130_000, 1_489)
+			.saturating_add(Weight::from_parts(18_420_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery NextLotteryId (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:0 w:1)
+	/// Storage: Lottery LotteryRandomness (r:0 w:1)
+	/// Storage: Lottery CreationDeposits (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn create_lottery() -> Weight {
+		Weight::from_parts(38_120_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket(p: u32, ) -> Weight {
+		Weight::from_parts(40_622_000, 3_593)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn award_prize(p: u32, ) -> Weight {
+		Weight::from_parts(66_570_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:1)
+	fn set_ticket_cost() -> Weight {
+		Weight::from_parts(11_980_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn cancel_lottery(p: u32, ) -> Weight {
+		Weight::from_parts(29_840_000, 3_593)
+			.saturating_add(Weight::from_parts(27_420_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn leave_lottery(p: u32, ) -> Weight {
+		Weight::from_parts(38_840_000, 3_593)
+			.saturating_add(Weight::from_parts(101_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:4 w:4)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn draw(p: u32, ) -> Weight {
+		Weight::from_parts(87_910_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:1 w:1)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(14_870_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:1 w:1)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(14_610_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery SeedCommitments (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	fn commit_seed() -> Weight {
+		Weight::from_parts(14_980_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery SeedCommitments (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery RoundDrawn (r:0 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: Lottery JackpotCarryover (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn reveal_and_draw(p: u32, ) -> Weight {
+		Weight::from_parts(77_450_000, 6_196)
+			.saturating_add(Weight::from_parts(27_530_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	fn donate() -> Weight {
+		Weight::from_parts(35_250_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim_prize() -> Weight {
+		Weight::from_parts(37_730_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	/// The range of component `n` is `[0, 500]`.
+	fn sweep_expired(n: u32, ) -> Weight {
+		Weight::from_parts(15_590_000, 3_513)
+			.saturating_add(Weight::from_parts(14_260_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_531).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_for(p: u32, ) -> Weight {
+		Weight::from_parts(54_840_000, 6_196)
+			.saturating_add(Weight::from_parts(97_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `n` is `[1, 50]`.
+	fn buy_tickets(n: u32, ) -> Weight {
+		Weight::from_parts(32_520_000, 3_593)
+			.saturating_add(Weight::from_parts(44_850_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_206).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Paused (r:0 w:1)
+	fn pause() -> Weight {
+		Weight::from_parts(11_040_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Paused (r:0 w:1)
+	fn unpause() -> Weight {
+		Weight::from_parts(10_960_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery PendingPayouts (r:2 w:1)
+	/// Storage: Lottery PayoutTotals (r:1 w:1)
+	/// Storage: Lottery UnclaimedPrizes (r:1 w:1)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn on_idle_payout() -> Weight {
+		Weight::from_parts(44_870_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn draw_unsigned(p: u32, ) -> Weight {
+		Weight::from_parts(68_440_000, 6_196)
+			.saturating_add(Weight::from_parts(27_480_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Referrals (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_with_referral(p: u32, ) -> Weight {
+		Weight::from_parts(51_980_000, 3_593)
+			.saturating_add(Weight::from_parts(181_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 96).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery TicketsIssued (r:1 w:1)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn force_add_participant(p: u32, ) -> Weight {
+		Weight::from_parts(26_420_000, 3_593)
+			.saturating_add(Weight::from_parts(91_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `p` is `[1, 500]`.
+	fn force_remove_participant(p: u32, ) -> Weight {
+		Weight::from_parts(43_370_000, 6_196)
+			.saturating_add(Weight::from_parts(102_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery TicketNumbers (r:1 w:1)
+	/// Storage: Lottery TicketOwners (r:0 w:10)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	/// The range of component `t` is `[1, 10]`.
+	fn reduce_tickets(p: u32, t: u32, ) -> Weight {
+		Weight::from_parts(40_230_000, 3_593)
+			.saturating_add(Weight::from_parts(101_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(2_910_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Paused (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:0)
+	/// Storage: Lottery PendingScratches (r:1 w:1)
+	/// Storage: Lottery JackpotCarryover (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn scratch() -> Weight {
+		Weight::from_parts(39_870_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:1)
+	fn set_max_participants() -> Weight {
+		Weight::from_parts(13_350_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	fn reset_nonce() -> Weight {
+		Weight::from_parts(8_230_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery FeeExempt (r:0 w:1)
+	fn add_fee_exempt() -> Weight {
+		Weight::from_parts(12_190_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery FeeExempt (r:0 w:1)
+	fn remove_fee_exempt() -> Weight {
+		Weight::from_parts(12_080_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery JackpotCarryover (r:1 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn emergency_withdraw() -> Weight {
+		Weight::from_parts(48_170_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery TicketMemos (r:0 w:1)
+	/// The range of component `p` is `[0, 499]`.
+	fn buy_ticket_with_data(p: u32, ) -> Weight {
+		Weight::from_parts(43_317_000, 3_593)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 48).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Lottery Enabled (r:0 w:1)
+	fn enable_lottery() -> Weight {
+		Weight::from_parts(8_710_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:0 w:1)
+	fn disable_lottery() -> Weight {
+		Weight::from_parts(8_760_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery CancellationProgress (r:1 w:1)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[1, 500]`.
+	fn cancel_lottery_paged(p: u32, ) -> Weight {
+		Weight::from_parts(32_890_000, 3_593)
+			.saturating_add(Weight::from_parts(27_420_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Subscriptions (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn subscribe() -> Weight {
+		Weight::from_parts(31_240_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Subscriptions (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn unsubscribe() -> Weight {
+		Weight::from_parts(27_860_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:50 w:50)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	/// The range of component `n` is `[1, 50]`.
+	fn add_to_allowlist_batch(n: u32, ) -> Weight {
+		Weight::from_parts(13_420_000, 1_489)
+			.saturating_add(Weight::from_parts(6_310_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_475).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery AllowList (r:50 w:50)
+	/// Storage: Lottery AllowListCount (r:1 w:1)
+	/// The range of component `n` is `[1, 50]`.
+	fn remove_from_allowlist_batch(n: u32, ) -> Weight {
+		Weight::from_parts(13_180_000, 1_489)
+			.saturating_add(Weight::from_parts(6_540_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_475).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery RoundDrawn (r:1 w:1)
+	/// Storage: Lottery RoundEnd (r:1 w:1)
+	/// Storage: Lottery Participants (r:1 w:1)
+	/// Storage: Lottery ReservedFunds (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:1)
+	/// Storage: Lottery WinnerHistory (r:1 w:1)
+	/// Storage: Lottery LastDraw (r:0 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: Lottery CreationDeposits (r:1 w:1)
+	/// The range of component `p` is `[2, 500]`.
+	fn force_settle_stale_round(p: u32, ) -> Weight {
+		Weight::from_parts(71_240_000, 6_196)
+			.saturating_add(Weight::from_parts(27_560_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Lottery Enabled (r:1 w:0)
+	/// Storage: Lottery Lotteries (r:1 w:0)
+	/// Storage: Lottery Participants (r:1 w:0)
+	/// Storage: Lottery CurrentRound (r:1 w:0)
+	/// Storage: Lottery RoundPrizeSource (r:0 w:1)
+	fn set_prize_source() -> Weight {
+		Weight::from_parts(15_380_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Lottery PendingScratches (r:1 w:1)
+	/// Storage: Lottery Nonce (r:1 w:1)
+	/// Storage: System ParentHash (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: System Account (r:50 w:0)
+	/// Storage: Lottery TotalUnclaimed (r:50 w:50)
+	/// Storage: Lottery JackpotCarryover (r:50 w:50)
+	/// Storage: Lottery UnclaimedPrizes (r:50 w:50)
+	/// Storage: Lottery TotalPrizesPaid (r:1 w:1)
+	/// The range of component `n` is `[0, 50]`.
+	fn settle_scratches(n: u32, ) -> Weight {
+		Weight::from_parts(6_130_000, 1_489)
+			.saturating_add(Weight::from_parts(18_420_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
@@ -4,198 +4,4437 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+pub mod runtime_api;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+pub(crate) const LOG_TARGET: &str = "runtime::lottery";
+
+// The test helpers allow choosing the winners, so they must never reach an on-chain runtime
+#[cfg(all(feature = "test-helpers", not(feature = "std")))]
+compile_error!("The `test-helpers` feature can't be enabled on a Wasm runtime");
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 
 	use super::*;
 	use frame_support::pallet_prelude::{*, ValueQuery, OptionQuery};
 	use frame_system::pallet_prelude::*;
-	use scale_info::prelude::vec::Vec;
+	use scale_info::prelude::{collections::{BTreeMap, BTreeSet}, vec, vec::Vec};
+	use core::mem;
+	use frame_support::dispatch::{Pays, PostDispatchInfo};
+	use frame_support::sp_io::hashing::blake2_256;
+	use frame_support::sp_runtime::{offchain::storage::StorageValueRef, traits::SaturatedConversion};
+	use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
+	#[cfg(feature = "try-runtime")]
+	use frame_support::sp_runtime::TryRuntimeError;
+	#[cfg(not(feature = "fungibles"))]
+	use frame_support::traits::{ExistenceRequirement, WithdrawReasons};
+	#[cfg(feature = "xcm-payout")]
+	use frame_support::sp_runtime::traits::Convert;
+	#[cfg(feature = "xcm-payout")]
+	use xcm::v3::{
+		send_xcm, Fungibility, Instruction, MultiAsset, MultiAssetFilter, MultiLocation, SendXcm,
+		WeightLimit, WildMultiAsset, Xcm,
+	};
+	#[cfg(feature = "nfts")]
+	use frame_support::traits::tokens::nonfungibles;
+	#[cfg(feature = "scheduler")]
+	use frame_support::traits::{
+		schedule::{v3::Named as ScheduleNamed, DispatchTime, LOWEST_PRIORITY},
+		QueryPreimage, StorePreimage,
+	};
+	#[cfg(feature = "fungibles")]
+	use frame_support::traits::{
+		fungibles,
+		tokens::{Fortitude, Precision, Preservation},
+	};
 
 	use frame_support::
 	{
-		sp_runtime::traits::AccountIdConversion,
+		sp_runtime::{
+			traits::{
+				AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, Hash as HashT, Saturating, Zero,
+			},
+			Perbill, Permill, TransactionOutcome,
+		},
+		storage::with_transaction,
 		traits:: {
-			Currency, ExistenceRequirement, Randomness
+			BalanceStatus, BuildGenesisConfig, Currency, NamedReservableCurrency, OnRuntimeUpgrade, 
+			Randomness, UnixTime,
 		},
 		PalletId,
 	};
 
-	type BalanceOf<T> = 
+	pub type BalanceOf<T> = 
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	// Number of ticket numbers drawn for each winner of a round, before drawing
+	// the winners still missing among the tickets of the other participants
+	const MAX_SAMPLING_ATTEMPTS: u32 = 16;
+
+	// Maximum number of winners returned by a single page of the winners history
+	pub const MAX_HISTORY_PAGE: u32 = 100;
+
+	// Number of payouts made between two reports of the progress of a round's payouts
+	const PAYOUT_PROGRESS_INTERVAL: u32 = 10;
+
+	pub type LotteryId = u32;
+
+	#[cfg(feature = "scheduler")]
+	pub type CallOf<T> = <T as Config>::RuntimeCall;
+
+	/// Lets other modules react to the winners of the lotteries
+	pub trait OnWinnerSelected<AccountId, Balance> {
+		/// Called for each winner once the prize is awarded, with the amount won and
+		/// the number of draws the account won so far, including this one
+		fn on_winner(winner: &AccountId, prize: Balance, wins: u32);
+	}
+
+	impl<AccountId, Balance> OnWinnerSelected<AccountId, Balance> for () {
+		fn on_winner(_winner: &AccountId, _prize: Balance, _wins: u32) {}
+	}
+
+	/// Lets each deployment add its own rules on who can participate
+	pub trait EnsureParticipant<AccountId> {
+		/// Returns an error if the account can't participate in the lotteries
+		fn ensure_eligible(who: &AccountId) -> DispatchResult;
+	}
+
+	impl<AccountId> EnsureParticipant<AccountId> for () {
+		fn ensure_eligible(_who: &AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+
+	/// Tells whether an account has an identity verified by a registrar
+	pub trait VerifiedIdentity<AccountId> {
+		/// Returns whether the account's identity was judged at least reasonable
+		fn is_verified(who: &AccountId) -> bool;
+	}
+
+	impl<AccountId> VerifiedIdentity<AccountId> for () {
+		fn is_verified(_who: &AccountId) -> bool {
+			false
+		}
+	}
+
+	#[cfg(feature = "identity")]
+	impl<T: pallet_identity::Config> VerifiedIdentity<T::AccountId> for pallet_identity::Pallet<T> {
+		fn is_verified(who: &T::AccountId) -> bool {
+			pallet_identity::Pallet::<T>::identity(who).map_or(false, |registration| {
+				registration.judgements.iter().any(|(_, judgement)| matches!(
+					judgement,
+					pallet_identity::Judgement::Reasonable | pallet_identity::Judgement::KnownGood
+				))
+			})
+		}
+	}
+
+	// Where the randomness picking the winners comes from
+	#[derive(Clone, Copy)]
+	enum DrawSeed {
+		// The randomness module alone
+		OnChain,
+		// The randomness module, mixed with a seed revealed by the oracle
+		Revealed([u8; 32]),
+		// The given seed instead of the randomness module, for reproducible draws
+		#[cfg(feature = "test-helpers")]
+		Fixed([u8; 32]),
+	}
+
+	/// Settings of each lottery, chosen when the lottery is created
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct LotteryConfig<Balance> {
+		/// The price to charge for each ticket
+		pub ticket_cost: Balance,
+		/// The top limit of participants allowed in each round
+		pub max_participants: u32,
+	}
+
+	/// How the price of each ticket is computed
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum PricingStrategy<Balance> {
+		/// Every ticket costs the ticket cost of the lottery
+		Fixed,
+		/// Every ticket costs the base price, plus the step for each ticket already
+		/// sold in the round
+		Linear { base: Balance, step: Balance },
+	}
+
+	/// What happens when an account already participating buys tickets again
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum DuplicatePurchase {
+		/// The purchase fails
+		Reject,
+		/// The purchase succeeds without buying anything, and is free
+		Idempotent,
+		/// The tickets are added to the ones the account holds
+		AllowMultiple,
+	}
+
+	/// How often the lotteries are drawn automatically
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum DrawSchedule<BlockNumber> {
+		/// Every given number of blocks
+		Blocks(BlockNumber),
+		/// Every given number of milliseconds, as measured by the timestamps of the 
+		/// blocks. The timestamp of each block is set by its author, within the drift
+		/// the validators accept, so the authors can bring a draw forward or delay 
+		/// it by as much as that drift
+		Timestamp { interval_ms: u64 },
+	}
+
+	/// Where the remainder left by splitting a prize goes
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum DustDestination {
+		/// Added to the share of the first winner drawn
+		Winner,
+		/// Paid out to the fee destination, along with the fee
+		Treasury,
+		/// Carried over to the prize of the next round
+		Carryover,
+		/// Burned, along with the burned share of the prize
+		Burn,
+	}
+
+	/// Where the randomness drawing the rounds of a lottery comes from
+	#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum RandomnessSource {
+		/// The randomness module alone, so any draw can pick the winners
+		#[default]
+		OnChain,
+		/// The randomness module mixed with a seed committed and revealed by the 
+		/// oracle, so the rounds are only drawn by revealing the seed
+		CommitReveal,
+	}
+
+	/// What the prize of a round is made of
+	#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum PrizeSource<Balance> {
+		/// The funds in the lottery's account, including the ticket funds of the 
+		/// round, net of the fee, the burn and the seed of the next round
+		#[default]
+		Pool,
+		/// A preset amount paid whole out of the funds in the lottery's account 
+		/// before the round, such as a sponsor's donations, with the ticket funds 
+		/// of the round going entirely to the fee destination
+		Fixed { amount: Balance },
+	}
+
+	/// Prepaid entries of an account into the coming rounds of a lottery
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Subscription<Balance> {
+		/// The rounds the account is still to be entered into
+		pub remaining_rounds: u32,
+		/// The funds prepaid for those rounds, held until each entry is paid
+		pub funds: Balance,
+	}
+
+	/// State of the current round of a lottery, gathered in a single query
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct RoundInfo<Balance, BlockNumber> {
+		/// The round currently selling tickets or waiting to be drawn
+		pub round: u32,
+		/// The number of accounts participating in the round
+		pub participant_count: u32,
+		/// The prize the round would award, before the cap and the fee
+		pub pot: Balance,
+		/// The price of the next ticket sold
+		pub ticket_cost: Balance,
+		/// The block at which the ticket sales close, if the round has started
+		pub ends_at: Option<BlockNumber>,
+		/// Whether the lotteries are paused
+		pub paused: bool,
+	}
+
+	/// A winner of a lottery round, along with the funds the round was settled with
+	/// and the participants it was drawn over
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct WinnerRecord<AccountId, Balance> {
+		/// The round won
+		pub round: u32,
+		/// The account drawn
+		pub winner: AccountId,
+		/// The amount awarded to the winner
+		pub prize: Balance,
+		/// The fee charged on the round's prize
+		pub fee: Balance,
+		/// The amount of the round's prize burned
+		pub burned: Balance,
+		/// The balance of the lottery's account when the round was drawn, once the
+		/// ticket funds were collected and before any of them were paid out
+		pub pot: Balance,
+		/// The blake2_256 hash of the encoded accounts of the round's participants,
+		/// sorted and concatenated
+		pub participants_hash: [u8; 32],
+		/// The number of participants of the round
+		pub participant_count: u32,
+	}
+
+	// The current version of the storage layout, bumped by each migration
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the module by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 
 		// Event definition
 		type RuntimeEvent: From<Event<Self>> 
 			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		// Currency 
-		type Currency: Currency<Self::AccountId>;
+		// Currency, which must support named reserves as the tickets cost is reserved 
+		// on each buyer's own account until the prize is awarded, under the module's 
+		// `PalletId`, so it's kept apart from the funds other modules reserve
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+
+		// Identifier of the assets, when the tickets are paid in an asset
+		#[cfg(feature = "fungibles")]
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		// Assets, used instead of the currency to pay for the tickets and the prize
+		#[cfg(feature = "fungibles")]
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = BalanceOf<Self>>
+			+ fungibles::Mutate<Self::AccountId>;
+
+		// Asset the tickets are paid in
+		#[cfg(feature = "fungibles")]
+		#[pallet::constant]
+		type TicketAsset: Get<Self::AssetId>;
+
+		// Asset the prizes are paid in, and donations are made in. When it's not 
+		// the ticket asset, the prize pool is only funded by donations, and the 
+		// ticket funds of each round go to the fee destination once it's drawn
+		#[cfg(feature = "fungibles")]
+		#[pallet::constant]
+		type PrizeAsset: Get<Self::AssetId>;
 
 		// Randomness
 		type MyRandomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
 
-		// Ticket cost
+		// Randomness from the block authors' VRF outputs, such as the parent block 
+		// randomness of BABE, seeding the draws when available instead of the 
+		// randomness above. It must not be the randomness of the block being authored
+		#[cfg(feature = "vrf-randomness")]
+		type ValidatorRandomness: Randomness<Option<Self::Hash>, BlockNumberFor<Self>>;
+
+		// Minimum age of the randomness used for the draws, so the low entropy
+		// randomness of a freshly started chain is never used
+		#[pallet::constant]
+		type RandomnessDelay: Get<BlockNumberFor<Self>>;
+
+		// Maximum number of lotteries that can be created
+		#[pallet::constant]
+		type MaxLotteries: Get<u32>;
+
+		// Deposit reserved on the creator of each lottery, returned once the lottery 
+		// is drawn and slashed to the fee destination if it's cancelled before
 		#[pallet::constant]
-		type TicketCost: Get<BalanceOf<Self>>;
+		type LotteryCreationDeposit: Get<BalanceOf<Self>>;
 
-		// Maximum number of participants
+		// Maximum number of participants of any lottery, which bounds the 
+		// participants list
 		#[pallet::constant]
 		type MaxParticipants: Get<u32>;
 
+		// Maximum number of tickets each account can hold on a single round
+		#[pallet::constant]
+		type MaxTicketsPerAccount: Get<u32>;
+
+		// What happens when an account already participating buys tickets again
+		#[pallet::constant]
+		type DuplicatePurchase: Get<DuplicatePurchase>;
+
+		// Number of blocks an account must wait between purchases (zero disables it)
+		#[pallet::constant]
+		type BuyCooldown: Get<BlockNumberFor<Self>>;
+
+		// Maximum number of beneficiaries of a batch purchase
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+
+		// Maximum number of subscribers entered into the new rounds on each block
+		#[pallet::constant]
+		type MaxAutoEntriesPerBlock: Get<u32>;
+
+		// Maximum number of lotteries drawn or refunded automatically on each block,
+		// with the rest of the lotteries due left for the next blocks
+		#[pallet::constant]
+		type MaxAutoDrawsPerBlock: Get<u32>;
+
+		// Maximum length of the memo a buyer can attach to its tickets
+		#[pallet::constant]
+		type MaxMemo: Get<u32>;
+
+		// Whether the memos attached to the tickets are mixed into the randomness 
+		// of the draw. They are XORed with it, so they can't make the draw any 
+		// more predictable, but the buyers can't choose the result either
+		#[pallet::constant]
+		type MixMemoEntropy: Get<bool>;
+
+		// Minimum number of participants required to award the prize
+		#[pallet::constant]
+		type MinParticipants: Get<u32>;
+
+		// Minimum pot required to award the prize, including the jackpot carried 
+		// over and the donations, so rounds go on until the prize is worth winning
+		#[pallet::constant]
+		type MinPot: Get<BalanceOf<Self>>;
+
+		// Reward paid out of the prize to the account triggering a successful draw,
+		// so the accounts drawing the rounds are paid for their transaction fees
+		#[pallet::constant]
+		type DrawReward: Get<BalanceOf<Self>>;
+
+		// Maximum number of winners sharing the prize on each round
+		#[pallet::constant]
+		type WinnersPerRound: Get<u32>;
+
+		// Number of rounds of a lottery in a row an account must enter without winning
+		// to be guaranteed a win in the next round of the lottery it enters (zero disables it)
+		#[pallet::constant]
+		type PityThreshold: Get<u32>;
+
+		// Maximum number of past winners kept in the history
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+
+		// Number of blocks tickets can be bought for since the first ticket of
+		// a round is bought (zero keeps the sales open until the draw)
+		#[pallet::constant]
+		type SalesDuration: Get<BlockNumberFor<Self>>;
+
+		// Whether root can draw a round before the end of its ticket sales, as an
+		// emergency measure (otherwise even root waits for the deadline)
+		#[pallet::constant]
+		type AllowEarlyRootDraw: Get<bool>;
+
+		// How often the lotteries are drawn automatically, either in blocks or in 
+		// time (a zero interval disables them)
+		#[pallet::constant]
+		type DrawSchedule: Get<DrawSchedule<BlockNumberFor<Self>>>;
+
+		// Time of the current block, as kept by the timestamp module, to draw the
+		// lotteries on a schedule in time
+		type Timestamp: UnixTime;
+
+		// Number of blocks a round can go without being drawn since its first 
+		// ticket is bought before it's reported as stale (zero disables the report)
+		#[pallet::constant]
+		type StaleRoundBlocks: Get<BlockNumberFor<Self>>;
+
+		// Number of blocks the winners have to claim their prizes since the draw,
+		// before the prizes go back into the pot (zero keeps them forever)
+		#[pallet::constant]
+		type ClaimDeadline: Get<BlockNumberFor<Self>>;
+
 		// Module Id
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		// Origin allowed to manage the lottery settings
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		// Origin of the oracle allowed to commit and reveal the seeds mixed 
+		// with the on-chain randomness
+		type SeedOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		// Whether only the accounts in the allowlist can buy tickets
+		#[pallet::constant]
+		type RequireAllowList: Get<bool>;
+
+		// Whether a single event summing up each round is emitted once it's drawn, 
+		// on top of the events of each step of the draw
+		#[pallet::constant]
+		type EmitRoundSummary: Get<bool>;
+
+		// Whether only the accounts with a verified identity can buy tickets
+		#[pallet::constant]
+		type RequireIdentity: Get<bool>;
+
+		// Source of the verified identities, such as the identity module when the 
+		// `identity` feature is enabled, or () when they are not required
+		type Identity: VerifiedIdentity<Self::AccountId>;
+
+		// Custom rules on who can participate, such as staking or holding a token,
+		// checked on top of the allowlist and the identities
+		type EligibilityCheck: EnsureParticipant<Self::AccountId>;
+
+		// Weight information for the extrinsics in this module
+		type WeightInfo: WeightInfo;
+
+		// Percentage of the prize charged as a fee
+		#[pallet::constant]
+		type FeePercent: Get<Permill>;
+
+		// Percentage of the prize burned, taken from the prize along with the fee
+		#[pallet::constant]
+		type BurnPercent: Get<Permill>;
+
+		// Percentage of the prize kept to seed the prize of the next round, taken 
+		// from the prize along with the fee and the burn on every round
+		#[pallet::constant]
+		type SeedNextRoundPercent: Get<Permill>;
+
+		// Maximum prize awarded on a single round, before charging the fee, with 
+		// any excess carried over to the next round (None keeps it uncapped)
+		#[pallet::constant]
+		type MaxPrize: Get<Option<BalanceOf<Self>>>;
+
+		// Maximum funds held by each lottery at once, counting the ticket funds of 
+		// the current round, the pot and the prizes not claimed yet, above which 
+		// purchases and donations are refused (None keeps it uncapped)
+		#[pallet::constant]
+		type MaxEscrow: Get<Option<BalanceOf<Self>>>;
+
+		// How the price of each ticket is computed
+		#[pallet::constant]
+		type Pricing: Get<PricingStrategy<BalanceOf<Self>>>;
+
+		// Maximum price of a single ticket, at which a rising price saturates
+		#[pallet::constant]
+		type MaxTicketCost: Get<BalanceOf<Self>>;
+
+		// Minimum price of a single ticket, so every ticket pays at least for the
+		// storage it takes. Lower ticket costs are refused, and a rising price 
+		// starts from it
+		#[pallet::constant]
+		type MinTicketCost: Get<BalanceOf<Self>>;
+
+		// Account receiving the fee charged on each prize
+		type FeeDestination: Get<Self::AccountId>;
+
+		// Where the remainder left by splitting the prize between the winners goes
+		#[pallet::constant]
+		type DustDestination: Get<DustDestination>;
+
+		// Price of each scratch, which goes to the lottery's account
+		#[pallet::constant]
+		type ScratchCost: Get<BalanceOf<Self>>;
+
+		// Chance of each scratch to win the scratch prize
+		#[pallet::constant]
+		type ScratchOdds: Get<Perbill>;
+
+		// Prize paid out from the lottery's account to each winning scratch
+		#[pallet::constant]
+		type ScratchPrize: Get<BalanceOf<Self>>;
+
+		// Maximum number of scratches rolled on each block, which bounds the
+		// scratches bought to be rolled on the same block
+		#[pallet::constant]
+		type MaxScratchesPerBlock: Get<u32>;
+
+		// Priority of the unsigned draws submitted by the offchain worker
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		// Number of blocks an unsigned draw stays valid in the pool, and that 
+		// the offchain worker waits before submitting the same draw again
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+
+		// Router sending the messages that pay out the prizes on the payout location
+		#[cfg(feature = "xcm-payout")]
+		type XcmRouter: SendXcm;
+
+		// Chain where the winners receive the prizes paid out on idle blocks
+		#[cfg(feature = "xcm-payout")]
+		type PayoutLocation: Get<MultiLocation>;
+
+		// Asset of the prizes, as seen from the payout location
+		#[cfg(feature = "xcm-payout")]
+		type PayoutAsset: Get<MultiLocation>;
+
+		// Account of the payout location on this chain, holding the prizes paid out
+		// as the reserve of the asset deposited there
+		#[cfg(feature = "xcm-payout")]
+		type PayoutReserve: Get<Self::AccountId>;
+
+		// Converts the account of a winner into its location on the payout location
+		#[cfg(feature = "xcm-payout")]
+		type AccountIdToLocation: Convert<Self::AccountId, MultiLocation>;
+
+		// Identifier of the collections of the NFTs module
+		#[cfg(feature = "nfts")]
+		type CollectionId: Member + Parameter + Copy + MaxEncodedLen;
+
+		// Identifier of the items of the NFTs module, built from a counter
+		#[cfg(feature = "nfts")]
+		type ItemId: Member + Parameter + Copy + MaxEncodedLen + From<u32>;
+
+		// NFTs module minting an item for each ticket purchase
+		#[cfg(feature = "nfts")]
+		type Nfts: nonfungibles::Mutate<
+			Self::AccountId, 
+			CollectionId = Self::CollectionId, 
+			ItemId = Self::ItemId,
+		>;
+
+		// Collection of the items minted for the ticket purchases, created beforehand
+		// with the lottery's module account allowed to mint in it
+		#[cfg(feature = "nfts")]
+		#[pallet::constant]
+		type TicketCollection: Get<Self::CollectionId>;
+
+		// The overarching call type, to schedule the draws
+		#[cfg(feature = "scheduler")]
+		type RuntimeCall: Parameter 
+			+ From<Call<Self>> 
+			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+		// The overarching origin type, to schedule the draws as the lottery's account
+		#[cfg(feature = "scheduler")]
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		// Scheduler dispatching the draw of each round at the end of its ticket sales
+		#[cfg(feature = "scheduler")]
+		type Scheduler: ScheduleNamed<BlockNumberFor<Self>, CallOf<Self>, Self::PalletsOrigin>;
+
+		// Preimages provider, to bound the scheduled draw calls
+		#[cfg(feature = "scheduler")]
+		type Preimages: QueryPreimage + StorePreimage;
+
+		// Handler notified of each winner, once the prize is awarded
+		type OnWinner: OnWinnerSelected<Self::AccountId, BalanceOf<Self>>;
 	}
 
 	// The pallet's runtime storage items.
+	// The settings of every lottery created
 	#[pallet::storage]
-	#[pallet::getter(fn get_participants)]
-	pub(super) type Participants<T: Config> = StorageValue<
+	#[pallet::getter(fn get_lottery)]
+	pub(super) type Lotteries<T: Config> = StorageMap<
 		_,
-		BoundedVec<T::AccountId, T::MaxParticipants>,
+		Blake2_128Concat,
+		LotteryId,
+		LotteryConfig<BalanceOf<T>>,
 		OptionQuery
 	>;
 
+	// The identifier to be assigned to the next lottery created
 	#[pallet::storage]
-	#[pallet::getter(fn get_nonce)]
-	pub(super) type Nonce<T: Config> = StorageValue<
+	#[pallet::getter(fn get_next_lottery_id)]
+	pub(super) type NextLotteryId<T: Config> = StorageValue<
 		_,
-		u64,
+		LotteryId,
 		ValueQuery
 	>;
 
-	// Pallets use events to inform users when important changes are made.
-	// https://docs.substrate.io/main-docs/build/events-errors/
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Event emitted when a ticket is bought
-		TicketBought { who: T::AccountId },
-		/// Event emitted when the prize is awarded
-		PrizeAwarded { winner: T::AccountId },
-		/// Event emitted when the prize is to be awarded, but there are no participants
-		ThereAreNoParticipants,
-	}
+	// Each participant of a lottery is stored along with the number of tickets bought
+	#[cfg(not(feature = "indexed-participants"))]
+	#[pallet::storage]
+	#[pallet::getter(fn get_participants)]
+	pub(super) type Participants<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BoundedVec<(T::AccountId, u32), T::MaxParticipants>,
+		OptionQuery
+	>;
 
-	// Errors inform users that something went wrong
-	#[pallet::error]
-	pub enum Error<T> {
-		NotEnoughCurrency,
-		AccountAlreadyParticipating,
-		CanNotAddParticipant,
-	}
+	// The number of tickets held by each participant of a lottery, along with its
+	// position in the participants index, when the participants are indexed
+	#[cfg(feature = "indexed-participants")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_participant_tickets)]
+	pub(super) type ParticipantTickets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		(u32, u32),
+		OptionQuery
+	>;
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	// The participant at each position of a lottery's participants, when the 
+	// participants are indexed
+	#[cfg(feature = "indexed-participants")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_participant_at)]
+	pub(super) type ParticipantIndex<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		u32,
+		T::AccountId,
+		OptionQuery
+	>;
 
-		#[pallet::call_index(0)]
-		#[pallet::weight(0)]
-		pub fn buy_ticket(origin: OriginFor<T>) -> DispatchResult {
+	// The number of participants of each lottery, when the participants are indexed
+	#[cfg(feature = "indexed-participants")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_participant_count)]
+	pub(super) type ParticipantCount<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u32,
+		ValueQuery
+	>;
 
-			// 1. Validates the origin signature
-			let buyer = ensure_signed(origin)?;
+	// The number of tickets held by all the participants of each lottery, when 
+	// the participants are indexed
+	#[cfg(feature = "indexed-participants")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_tickets_held)]
+	pub(super) type TicketsHeld<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u32,
+		ValueQuery
+	>;
 
-			// 2. Checks that the user has enough balance to afford the ticket price
-			ensure!(
-				T::Currency::free_balance(&buyer) >= T::TicketCost::get(),
-				Error::<T>::NotEnoughCurrency
-			);
+	// The index of the current round of each lottery, starting at zero
+	#[pallet::storage]
+	#[pallet::getter(fn get_current_round)]
+	pub(super) type CurrentRound<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u32,
+		ValueQuery
+	>;
 
-			// 3. Checks that the user is not already participating
-			if let Some(participants) = Self::get_participants() {
-				ensure!(
-					!participants.contains(&buyer),
-					Error::<T>::AccountAlreadyParticipating
-				);
-			}
+	// The block number in which the first ticket of the current round was bought
+	#[pallet::storage]
+	#[pallet::getter(fn get_round_start)]
+	pub(super) type RoundStart<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BlockNumberFor<T>,
+		OptionQuery
+	>;
 
-			// 4. Adds the user as a new participant for the prize
-			match Self::get_participants() {
-				Some(mut participants) => { 
-					ensure!(
-						participants.try_push(buyer.clone()).is_ok(), 
-						Error::<T>::CanNotAddParticipant
-					);
-					Participants::<T>::set(Some(participants));
-				}, 
-				None => {
-					let mut participants = BoundedVec::new();
-					ensure!(
-						participants.try_push(buyer.clone()).is_ok(), 
-						Error::<T>::CanNotAddParticipant
-					);
-					Participants::<T>::set(Some(participants));
-				}
-			};
+	// The block number in which the ticket sales of the current round end
+	#[pallet::storage]
+	#[pallet::getter(fn get_round_end)]
+	pub(super) type RoundEnd<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BlockNumberFor<T>,
+		OptionQuery
+	>;
 
-			// 5. Transfers the ticket cost to the module's account
-			// to be hold until transferred to the winner
-			T::Currency::transfer(
-				&buyer, 
-				&Self::get_pallet_account(), 
-				T::TicketCost::get(), 
-				ExistenceRequirement::KeepAlive)?;
-			
-			// 6. Notify the event
-			Self::deposit_event(Event::TicketBought { who: buyer });
-			Ok(())
-		}
+	// Whether the current round of each lottery has already been drawn
+	#[pallet::storage]
+	#[pallet::getter(fn get_round_drawn)]
+	pub(super) type RoundDrawn<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		bool,
+		ValueQuery
+	>;
 
-		#[pallet::call_index(1)]
-		#[pallet::weight(0)]
-		pub fn award_prize(origin: OriginFor<T>) -> DispatchResult {
+	// The funds reserved on each participant's account for the tickets bought
+	// in the current round of each lottery
+	#[pallet::storage]
+	#[pallet::getter(fn get_reserved_funds)]
+	pub(super) type ReservedFunds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
 
-			// 1. Validates the origin signature
-			let _who = ensure_root(origin)?;
+	// The block number in which the last draw of each lottery took place
+	#[pallet::storage]
+	#[pallet::getter(fn get_last_draw)]
+	pub(super) type LastDraw<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BlockNumberFor<T>,
+		ValueQuery
+	>;
 
-			match Self::get_participants() {
-				Some(participants) => { 
-					
-					// 2. Gets a random number from the randomness module
-					let nonce = Self::get_and_increment_nonce();
-					let (random_seed, _) = T::MyRandomness::random(&nonce);
-					let random_number = <u32>::decode(&mut random_seed.as_ref())
-						.expect("secure hashes should always be bigger than u32; qed");
-					
-					// 3. Selects the winner from the participants lit
-					let winner_index = random_number as usize % participants.len();
-					let winner = participants.as_slice().get(winner_index).unwrap();
-
-					// 4. Transfers the total prize to the winner's account
-					let prize = T::Currency::free_balance(&Self::get_pallet_account());
-					T::Currency::transfer(
-						&Self::get_pallet_account(), 
-						&winner, 
-						prize, 
-						ExistenceRequirement::AllowDeath)?;
-
-					// 5. Resets the participants list, and gets ready for another lottery round
-					Participants::<T>::kill();
-
-					// 6. Notify the event
-					Self::deposit_event(Event::PrizeAwarded { winner: winner.clone() } );
-				}, 
-				None => {
-					// Notify the event (No participants)
-					Self::deposit_event(Event::ThereAreNoParticipants);
-				}
-			};
+	// The timestamp, in milliseconds, of the last draw of each lottery, only kept 
+	// when drawing on a schedule in time
+	#[pallet::storage]
+	#[pallet::getter(fn get_last_draw_timestamp)]
+	pub(super) type LastDrawTimestamp<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u64,
+		ValueQuery
+	>;
+
+	// The most recent winners of each lottery, oldest first, each with the 
+	// balance the round was drawn with, so the payouts can be reconciled
+	#[pallet::storage]
+	#[pallet::getter(fn get_winner_history)]
+	pub(super) type WinnerHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BoundedVec<WinnerRecord<T::AccountId, BalanceOf<T>>, T::MaxHistory>,
+		ValueQuery
+	>;
+
+	// The participants of the most recent rounds of each lottery, sorted by 
+	// encoded account, whose accounts are hashed in the winners history. As many
+	// rounds are kept as
+	// the maximum number of winners in the history
+	#[cfg(feature = "full-snapshots")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_round_participants)]
+	pub(super) type RoundParticipants<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		u32,
+		BoundedVec<(T::AccountId, u32), T::MaxParticipants>,
+		OptionQuery
+	>;
+
+	// The rounds of each lottery whose participants are archived, oldest first, 
+	// so the oldest one is forgotten when a new round is archived, whichever
+	// rounds were cancelled or rolled over in between
+	#[cfg(feature = "full-snapshots")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_archived_rounds)]
+	pub(super) type ArchivedRounds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BoundedVec<u32, T::MaxHistory>,
+		ValueQuery
+	>;
+
+	// The balance rolled over from rounds that ended without participants, along
+	// with the donations and the scratch revenue, which is added to the prize of
+	// the next awarded round. It's owed to the next winners, so it can't be 
+	// withdrawn as stray funds
+	#[pallet::storage]
+	#[pallet::getter(fn get_jackpot_carryover)]
+	pub(super) type JackpotCarryover<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// The scratches bought and not rolled yet, by the block they are rolled on.
+	// Each one is rolled on the randomness of a block after its purchase, so its
+	// outcome can't be looked up before buying it
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_scratches)]
+	pub(super) type PendingScratches<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(LotteryId, T::AccountId), T::MaxScratchesPerBlock>,
+		ValueQuery
+	>;
+
+	// The creator of each lottery and the deposit reserved on its account, until 
+	// the lottery is drawn or cancelled
+	#[pallet::storage]
+	#[pallet::getter(fn get_creation_deposit)]
+	pub(super) type CreationDeposits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		(T::AccountId, BalanceOf<T>),
+		OptionQuery
+	>;
+
+	// The participants refunded so far, and the funds that could not be refunded, 
+	// for each lottery whose round is being cancelled over several calls. The round
+	// can't be drawn nor sell tickets until the cancellation is complete
+	#[pallet::storage]
+	#[pallet::getter(fn get_cancellation_progress)]
+	pub(super) type CancellationProgress<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		(u32, BalanceOf<T>),
+		OptionQuery
+	>;
+
+	// What the prize of the current round of each lottery is made of, until the 
+	// round is over
+	#[pallet::storage]
+	#[pallet::getter(fn get_prize_source)]
+	pub(super) type RoundPrizeSource<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		PrizeSource<BalanceOf<T>>,
+		ValueQuery
+	>;
+
+	// The subscriptions of each account to the coming rounds of each lottery
+	#[pallet::storage]
+	#[pallet::getter(fn get_subscription)]
+	pub(super) type Subscriptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		Subscription<BalanceOf<T>>,
+		OptionQuery
+	>;
+
+	// The last round of each lottery every subscriber was entered into
+	#[pallet::storage]
+	#[pallet::getter(fn get_auto_entered_round)]
+	pub(super) type AutoEnteredRound<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u32,
+		OptionQuery
+	>;
+
+	// The round of each lottery whose subscribers are being entered over several
+	// blocks, along with the last subscriber entered
+	#[pallet::storage]
+	#[pallet::getter(fn get_auto_entry_cursor)]
+	pub(super) type AutoEntryCursor<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		(u32, T::AccountId),
+		OptionQuery
+	>;
+
+	// The randomness source each lottery is drawn with, chosen when it's created
+	#[pallet::storage]
+	#[pallet::getter(fn get_lottery_randomness)]
+	pub(super) type LotteryRandomness<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		RandomnessSource,
+		ValueQuery
+	>;
+
+	// The hash of the seed committed for the current round of each lottery, which 
+	// has to be revealed to draw the round
+	#[pallet::storage]
+	#[pallet::getter(fn get_seed_commitment)]
+	pub(super) type SeedCommitments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		[u8; 32],
+		OptionQuery
+	>;
+
+	// The prizes owed to each winner of each lottery, kept in the lottery's 
+	// account until claimed, along with the block of the winner's last draw
+	#[pallet::storage]
+	#[pallet::getter(fn get_unclaimed_prize)]
+	pub(super) type UnclaimedPrizes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		(BalanceOf<T>, BlockNumberFor<T>),
+		OptionQuery
+	>;
+
+	// The total of the prizes not claimed yet of each lottery, which is not 
+	// part of the pot of the next rounds
+	#[pallet::storage]
+	#[pallet::getter(fn get_total_unclaimed)]
+	pub(super) type TotalUnclaimed<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// Whether the lotteries are stopped in an emergency
+	#[pallet::storage]
+	#[pallet::getter(fn get_paused)]
+	pub(super) type Paused<T: Config> = StorageValue<
+		_,
+		bool,
+		ValueQuery
+	>;
+
+	// Whether the lotteries are turned on, so the module can be deployed dormant 
+	// until governance enables it. The calls giving funds back are always available
+	#[pallet::storage]
+	#[pallet::getter(fn get_enabled)]
+	pub(super) type Enabled<T: Config> = StorageValue<
+		_,
+		bool,
+		ValueQuery
+	>;
+
+	// The winners of each awarded round whose prizes are still to be paid out 
+	// on idle blocks
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_payouts)]
+	pub(super) type PendingPayouts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		u32,
+		BoundedVec<T::AccountId, T::MaxParticipants>,
+		OptionQuery
+	>;
+
+	// The number of winners whose payouts were queued for each round, kept while 
+	// the payouts are pending
+	#[pallet::storage]
+	#[pallet::getter(fn get_payout_total)]
+	pub(super) type PayoutTotals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		u32,
+		u32,
+		ValueQuery
+	>;
+
+	// The number of drawn rounds of each lottery in a row each account entered 
+	// without winning, only kept while the pity threshold is enabled. Each lottery
+	// keeps its own streaks, so losing in one never earns a win in another
+	#[pallet::storage]
+	#[pallet::getter(fn get_loss_streak)]
+	pub(super) type LossStreak<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	// The accounts allowed to buy tickets when the allowlist is required
+	#[pallet::storage]
+	#[pallet::getter(fn get_allow_list)]
+	pub(super) type AllowList<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery
+	>;
+
+	// The number of accounts in the allowlist
+	#[pallet::storage]
+	#[pallet::getter(fn get_allowlist_count)]
+	pub(super) type AllowListCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// The block number of the last purchase signed by each account, only kept 
+	// while the buy cooldown is enabled
+	#[pallet::storage]
+	#[pallet::getter(fn get_last_buy)]
+	pub(super) type LastBuy<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery
+	>;
+
+	// The accounts exempt from the fee on the prizes they win. A round's fee is
+	// only waived when every winner of the round is exempt
+	#[pallet::storage]
+	#[pallet::getter(fn get_fee_exempt)]
+	pub(super) type FeeExempt<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery
+	>;
+
+	// The items minted for the ticket purchases of each participant in the current round
+	#[cfg(feature = "nfts")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_ticket_items)]
+	pub(super) type TicketItems<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::ItemId, T::MaxTicketsPerAccount>,
+		ValueQuery
+	>;
+
+	// The counter the id of the next ticket item is built from
+	#[cfg(feature = "nfts")]
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_ticket_item)]
+	pub(super) type NextTicketItem<T: Config> = StorageValue<
+		_,
+		u32,
+		ValueQuery
+	>;
+
+	// The owner of each ticket number issued in the current round. The numbers 
+	// are kept contiguous: the last one issued takes the place of a refunded one
+	#[pallet::storage]
+	#[pallet::getter(fn get_ticket_owner)]
+	pub(super) type TicketOwners<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		u32,
+		T::AccountId,
+		OptionQuery
+	>;
+
+	// The ticket numbers held by each participant in the current round
+	#[pallet::storage]
+	#[pallet::getter(fn get_ticket_numbers)]
+	pub(super) type TicketNumbers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<u32, T::MaxTicketsPerAccount>,
+		ValueQuery
+	>;
+
+	// The memo attached by each participant to its tickets in the current round, 
+	// such as a lucky number shown on the tickets
+	#[pallet::storage]
+	#[pallet::getter(fn get_ticket_memo)]
+	pub(super) type TicketMemos<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<u8, T::MaxMemo>,
+		OptionQuery
+	>;
+
+	// The number of tickets issued in the current round, which is the number 
+	// of the next ticket issued
+	#[pallet::storage]
+	#[pallet::getter(fn get_tickets_issued)]
+	pub(super) type TicketsIssued<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LotteryId,
+		u32,
+		ValueQuery
+	>;
+
+	// The tickets sold by every lottery since genesis, along with the bonus
+	// tickets granted for referrals, without the ones refunded when leaving a
+	// lottery or when a lottery round is cancelled
+	#[pallet::storage]
+	#[pallet::getter(fn get_total_tickets_sold)]
+	pub(super) type TotalTicketsSold<T: Config> = StorageValue<
+		_,
+		u64,
+		ValueQuery
+	>;
+
+	// The prizes awarded to the winners of every lottery since genesis,
+	// without the fees, whether they are claimed already or not
+	#[pallet::storage]
+	#[pallet::getter(fn get_total_prizes_paid)]
+	pub(super) type TotalPrizesPaid<T: Config> = StorageValue<
+		_,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// The number of draws won by each account since genesis, across every lottery
+	#[pallet::storage]
+	#[pallet::getter(fn get_lifetime_wins)]
+	pub(super) type LifetimeWins<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	// The prizes awarded to each account on the draws it won since genesis, 
+	// across every lottery, whether they are claimed already or not
+	#[pallet::storage]
+	#[pallet::getter(fn get_lifetime_winnings)]
+	pub(super) type LifetimeWinnings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// The accounts referred by each referrer, which was already granted
+	// its bonus ticket for them
+	#[pallet::storage]
+	#[pallet::getter(fn get_referral)]
+	pub(super) type Referrals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		(),
+		OptionQuery
+	>;
+
+	// The nonce mixed into the subject of each random number, which wraps around
+	// on overflow
+	#[pallet::storage]
+	#[pallet::getter(fn get_nonce)]
+	pub(super) type Nonce<T: Config> = StorageValue<
+		_,
+		u64,
+		ValueQuery
+	>;
+
+	// Optionally starts the chain with a running lottery, created with the given
+	// ticket cost and pre-seeded with the given participants and their tickets.
+	// The lotteries are dormant until enabled, unless enabled at genesis
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub enabled: bool,
+		pub ticket_cost: Option<BalanceOf<T>>,
+		pub participants: Vec<(T::AccountId, u32)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Enabled::<T>::put(self.enabled);
+
+			// 1. Checks that a ticket cost is supplied to create the lottery
+			let Some(ticket_cost) = self.ticket_cost else {
+				assert!(
+					self.participants.is_empty(),
+					"Genesis participants require a ticket cost to create the lottery"
+				);
+				return;
+			};
+			assert!(!ticket_cost.is_zero(), "Genesis ticket cost must not be zero");
+			assert!(ticket_cost >= T::MinTicketCost::get(), "Genesis ticket cost is below the minimum");
+
+			// 2. Checks the participants, which must be unique and fit the bounds
+			let mut seen = BTreeSet::new();
+			for (who, tickets) in self.participants.iter() {
+				assert!(seen.insert(who), "Duplicate genesis participant {:?}", who);
+				assert!(
+					*tickets > 0 && *tickets <= T::MaxTicketsPerAccount::get(),
+					"Invalid number of tickets for genesis participant {:?}",
+					who
+				);
+			}
+			let participants: BoundedVec<_, T::MaxParticipants> = self.participants
+				.clone()
+				.try_into()
+				.expect("Too many genesis participants");
+
+			// 3. Creates the lottery, which can't participate in itself
+			let lottery_id = Pallet::<T>::get_next_lottery_id();
+			let lottery_account = Pallet::<T>::get_pallet_account(lottery_id);
+			assert!(
+				participants.iter().all(|(who, _)| *who != lottery_account),
+				"Genesis participants can't include the lottery's own account"
+			);
+			Lotteries::<T>::insert(lottery_id, LotteryConfig {
+				ticket_cost,
+				max_participants: T::MaxParticipants::get(),
+			});
+			NextLotteryId::<T>::put(lottery_id.saturating_add(1));
+
+			// 4. Holds the tickets cost of each participant, as on any purchase, 
+			// and starts the first round
+			if participants.is_empty() {
+				return;
+			}
+			for (who, tickets) in participants.iter() {
+				let cost = ticket_cost.saturating_mul((*tickets).into());
+				Pallet::<T>::hold_ticket_funds(lottery_id, who, who, cost)
+					.expect("Genesis participants must afford their tickets");
+				ReservedFunds::<T>::insert(lottery_id, who, cost);
+				Pallet::<T>::set_tickets(lottery_id, who, *tickets)
+					.expect("Genesis participants fit the participants bound");
+				Pallet::<T>::issue_tickets(lottery_id, who, *tickets)
+					.expect("Genesis participants hold no more tickets than the limit per account");
+				TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_add((*tickets).into()));
+			}
+			Pallet::<T>::start_sales(lottery_id);
+		}
+	}
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/main-docs/build/events-errors/
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Event emitted when a new lottery is created
+		LotteryCreated { id: LotteryId },
+		/// Event emitted when the deposit for creating a lottery is reserved
+		CreationDepositReserved { lottery_id: LotteryId, creator: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the deposit for creating a lottery is returned, once
+		/// the lottery is drawn
+		CreationDepositReturned { lottery_id: LotteryId, creator: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the deposit for creating a lottery is slashed, as the 
+		/// lottery is cancelled without ever being drawn
+		CreationDepositSlashed { lottery_id: LotteryId, creator: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the first ticket of a new round is bought
+		RoundStarted { lottery_id: LotteryId, round: u32 },
+		/// Event emitted when a round goes undrawn for longer than the stale round
+		/// blocks, with the number of blocks since its first ticket was bought
+		RoundStale { lottery_id: LotteryId, round: u32, age: BlockNumberFor<T> },
+		/// Event emitted when root settles a round left undrawn for too long
+		StaleRoundSettled { lottery_id: LotteryId, round: u32 },
+		/// Event emitted when one or more tickets are bought
+		TicketBought { 
+			lottery_id: LotteryId, 
+			round: u32, 
+			who: T::AccountId, 
+			payer: T::AccountId, 
+			quantity: u32,
+			cost: BalanceOf<T>,
+		},
+		/// Event emitted when a batch of tickets is bought, one for each beneficiary
+		TicketsBought { lottery_id: LotteryId, payer: T::AccountId, count: u32 },
+		/// Event emitted when a referrer is granted a bonus ticket for the account referred
+		ReferralRewarded { lottery_id: LotteryId, referrer: T::AccountId, referred: T::AccountId },
+		/// Event emitted when an account prepays its entries into the coming rounds
+		Subscribed { lottery_id: LotteryId, who: T::AccountId, rounds: u32, funds: BalanceOf<T> },
+		/// Event emitted when a subscriber is entered into a round, with the rounds
+		/// it is still to be entered into
+		AutoEntered { lottery_id: LotteryId, round: u32, who: T::AccountId, remaining_rounds: u32 },
+		/// Event emitted when a subscription ends, either cancelled by the subscriber
+		/// or run through, with the prepaid funds refunded
+		Unsubscribed { lottery_id: LotteryId, who: T::AccountId, refunded: BalanceOf<T> },
+		/// Event emitted when root draws a round before the end of its ticket sales
+		EarlyDrawForced { lottery_id: LotteryId, round: u32, sales_end: BlockNumberFor<T> },
+		/// Event emitted when an account wins a round without being drawn, after
+		/// losing as many rounds in a row as the pity threshold
+		PityAwarded { lottery_id: LotteryId, round: u32, who: T::AccountId, streak: u32 },
+		/// Event emitted when a round is refunded to its participants, as its sales closed
+		/// without enough participants to draw it
+		RoundRefundedInsufficientParticipants { lottery_id: LotteryId, round: u32, refunded: u32 },
+		/// Event emitted when a ticket is refunded to a participant leaving the lottery
+		TicketRefunded { lottery_id: LotteryId, who: T::AccountId },
+		/// Event emitted when a participant gives back some of its tickets, which
+		/// are refunded
+		TicketsReduced { lottery_id: LotteryId, who: T::AccountId, amount: u32 },
+		/// Event emitted when the maximum number of participants is reached
+		LotteryFull { lottery_id: LotteryId, round: u32 },
+		/// Event emitted when the prize of a round is awarded to its winners
+		PrizesAwarded {
+			/// The lottery drawn
+			lottery_id: LotteryId,
+			/// The round drawn
+			round: u32,
+			/// Each winner along with the prize awarded to it, net of the fee, the
+			/// burned amount and the amount retained. The winners guaranteed by the
+			/// pity threshold are listed first
+			winners: Vec<(T::AccountId, BalanceOf<T>)>,
+			/// The number of the ticket each drawn winner was drawn with, leaving out
+			/// the winners guaranteed by the pity threshold. Empty when every
+			/// participant wins
+			winning_numbers: Vec<u32>,
+			/// The fee charged on the prize
+			fee_amount: BalanceOf<T>,
+			/// The amount of the prize burned
+			burned: BalanceOf<T>,
+			/// The amount of the prize retained to seed the next round
+			retained: BalanceOf<T>,
+			/// The balance of the lottery's account before any payout
+			pot: BalanceOf<T>,
+			/// The ticket funds collected for the round, in the ticket asset when
+			/// paying in assets, while the other amounts are in the prize asset
+			ticket_revenue: BalanceOf<T>,
+			/// The hash of the memos mixed into the randomness, when they are mixed in
+			memo_hash: Option<T::Hash>,
+			/// The reward paid to the account that triggered the draw
+			draw_reward: BalanceOf<T>,
+			/// What the prize was made of
+			prize_source: PrizeSource<BalanceOf<T>>,
+		},
+		/// Event emitted once a round is drawn and its prize awarded, summing up the
+		/// round: its participants and tickets, the balance it was drawn with, the 
+		/// winners and their prizes, the fee, the burned amount, the amount carried 
+		/// over to the next round, and the block of the randomness drawn with, which
+		/// is the block of the draw when every participant wins
+		RoundCompleted {
+			lottery_id: LotteryId,
+			round: u32,
+			participant_count: u32,
+			tickets_sold: u32,
+			pot: BalanceOf<T>,
+			winners: Vec<(T::AccountId, BalanceOf<T>)>,
+			fee: BalanceOf<T>,
+			burned: BalanceOf<T>,
+			carryover_out: BalanceOf<T>,
+			randomness_block: BlockNumberFor<T>,
+		},
+		/// Event emitted when the prize is to be awarded, but there are no participants
+		ThereAreNoParticipants { lottery_id: LotteryId },
+		/// Event emitted when a round ends without participants and its pot is
+		/// carried over to the next round
+		JackpotRolledOver { lottery_id: LotteryId, amount: BalanceOf<T>, new_round: u32 },
+		/// Event emitted when the prize exceeds the maximum prize, with the amount 
+		/// awarded and the excess carried over to the next round
+		PrizeCapped { lottery_id: LotteryId, round: u32, paid: BalanceOf<T>, carried: BalanceOf<T> },
+		/// Event emitted for each winner drawn, before the prize is paid out, with the
+		/// number of the winning ticket and the block the randomness was generated at
+		DrawComputed { 
+			lottery_id: LotteryId, 
+			round: u32, 
+			winning_number: u32, 
+			randomness_block: BlockNumberFor<T>,
+		},
+		/// Event emitted when the ticket cost is changed
+		TicketCostChanged { lottery_id: LotteryId, old: BalanceOf<T>, new: BalanceOf<T> },
+		/// Event emitted when the prize source of the lottery's current round is set
+		PrizeSourceSet { lottery_id: LotteryId, round: u32, source: PrizeSource<BalanceOf<T>> },
+		/// Event emitted when the maximum number of participants of a lottery is changed
+		MaxParticipantsChanged { lottery_id: LotteryId, old: u32, new: u32 },
+		/// Event emitted when the lottery is cancelled and the participants refunded
+		LotteryCancelled { lottery_id: LotteryId, refunded: u32 },
+		/// Event emitted when a page of participants is refunded, with the participants
+		/// still to be refunded before the round is cancelled
+		CancellationProgressed { lottery_id: LotteryId, refunded: u32, remaining: u32 },
+		/// Event emitted when the lottery's account can't cover all the refunds
+		RefundShortfall { lottery_id: LotteryId, missing: BalanceOf<T> },
+		/// Event emitted when an account is added to the allowlist
+		AddedToAllowList { who: T::AccountId },
+		/// Event emitted when an account is removed from the allowlist
+		RemovedFromAllowList { who: T::AccountId },
+		/// Event emitted when a batch of accounts is added to or removed from the 
+		/// allowlist, with the number of accounts actually added and removed
+		AllowListUpdated { added: u32, removed: u32 },
+		/// Event emitted when an account is exempted from the fee on its prizes
+		FeeExemptAdded { who: T::AccountId },
+		/// Event emitted when an account is charged the fee on its prizes again
+		FeeExemptRemoved { who: T::AccountId },
+		/// Event emitted when the hash of the seed for the round is committed
+		SeedCommitted { lottery_id: LotteryId, round: u32 },
+		/// Event emitted when the seed for the round is revealed, before the draw
+		SeedRevealed { lottery_id: LotteryId, round: u32, seed: [u8; 32] },
+		/// Event emitted when the prize of the lottery is increased with a donation
+		Donated { lottery_id: LotteryId, who: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when a scratch is bought, with the block it's rolled on
+		ScratchBought { lottery_id: LotteryId, who: T::AccountId, settles_at: BlockNumberFor<T> },
+		/// Event emitted when a scratch is rolled, with the prize owed if it won
+		ScratchResult { lottery_id: LotteryId, who: T::AccountId, won: bool, amount: BalanceOf<T> },
+		/// Event emitted when a prize is awarded, and can be claimed by the winner
+		PrizeClaimable { lottery_id: LotteryId, winner: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the winner claims a prize
+		PrizeClaimed { lottery_id: LotteryId, winner: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when a prize is not claimed in time, and goes back into the pot
+		PrizeExpired { lottery_id: LotteryId, who: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the payouts of every winner of the round are done
+		RoundFullyPaid { lottery_id: LotteryId, round: u32 },
+		/// Event emitted every few payouts of a round's winners, with the number of
+		/// winners paid so far out of the winners of the round
+		PayoutProgress { lottery_id: LotteryId, round: u32, paid_so_far: u32, total: u32 },
+		/// Event emitted when the prize of a winner could not be sent to the payout 
+		/// location, leaving it to be claimed on this chain
+		PayoutFailed { lottery_id: LotteryId, who: T::AccountId },
+		/// Event emitted when the admin registers a participant without charging it
+		ParticipantForceAdded { lottery_id: LotteryId, who: T::AccountId },
+		/// Event emitted when the admin removes a participant without refunding it
+		ParticipantForceRemoved { lottery_id: LotteryId, who: T::AccountId },
+		/// Event emitted when the lotteries are paused
+		LotteryPaused,
+		/// Event emitted when the lotteries are resumed
+		LotteryUnpaused,
+		/// Event emitted when the lotteries are turned on
+		LotteryEnabled,
+		/// Event emitted when the lotteries are turned off
+		LotteryDisabled,
+		/// Event emitted when the nonce of the random numbers is reset, with its
+		/// value before the reset
+		NonceReset { previous: u64 },
+		/// Event emitted when funds not owed to anyone are withdrawn from the lottery's
+		/// account by root
+		EmergencyWithdrawal { lottery_id: LotteryId, dest: T::AccountId, amount: BalanceOf<T> },
+	}
+
+	// Errors inform users that something went wrong
+	#[pallet::error]
+	pub enum Error<T> {
+		LotteryNotFound,
+		TooManyLotteries,
+		InvalidMaxParticipants,
+		NotAllowed,
+		NotEnoughCurrency,
+		CanNotAddParticipant,
+		InvalidQuantity,
+		TicketLimitReached,
+		LotteryFull,
+		NotParticipating,
+		SalesClosed,
+		DrawNotYetAllowed,
+		RoundAlreadyDrawn,
+		InvalidTicketCost,
+		NotEnoughParticipants,
+		SeedAlreadyCommitted,
+		SeedNotCommitted,
+		SeedRevealRequired,
+		InvalidSeed,
+		InvalidAmount,
+		NoPrizeToClaim,
+		PrizeExpired,
+		LotteryPaused,
+		InvalidParticipant,
+		PayoutNotSent,
+		NoVerifiedIdentity,
+		PotTooSmall,
+		SelfReferral,
+		AccountAlreadyParticipating,
+		RandomnessNotReady,
+		ArithmeticOverflow,
+		InsufficientTickets,
+		CapBelowCurrent,
+		PrizeBelowExistentialDeposit,
+		BuyTooSoon,
+		NoStrayFunds,
+		LotteryDisabled,
+		LotteryCancelling,
+		EscrowCapExceeded,
+		AlreadySubscribed,
+		NotSubscribed,
+		RoundNotStale,
+		RoundAlreadyStarted,
+		TicketCostTooLow,
+		TooManyScratches,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+
+			// Pays out the prizes owed to the winners, one at a time while there is
+			// enough weight left in the block, resuming on the next idle block
+			let payout_weight = T::WeightInfo::on_idle_payout();
+			let mut consumed = T::DbWeight::get().reads(1);
+			while remaining_weight.all_gte(consumed.saturating_add(payout_weight)) {
+				let Some((lottery_id, round, mut winners)) = PendingPayouts::<T>::iter().next() else {
+					break;
+				};
+				consumed.saturating_accrue(payout_weight);
+
+				// 1. Pays out the next winner. Prizes already claimed or expired are 
+				// skipped, and the ones failing to be paid are kept to be claimed
+				#[cfg(not(feature = "xcm-payout"))]
+				if let Some(winner) = winners.pop() {
+					let _ = with_transaction(|| match Self::do_claim_prize(lottery_id, winner) {
+						Ok(()) => TransactionOutcome::Commit(Ok(())),
+						Err(error) => TransactionOutcome::Rollback(Err(error)),
+					});
+				}
+
+				// 1. Pays out the next winner on the payout location. Prizes already
+				// claimed or expired are skipped, and the ones failing to be sent are 
+				// kept to be claimed on this chain
+				#[cfg(feature = "xcm-payout")]
+				if let Some(winner) = winners.pop() {
+					let paid = with_transaction(|| match Self::do_send_prize(lottery_id, &winner) {
+						Ok(()) => TransactionOutcome::Commit(Ok(())),
+						Err(error) => TransactionOutcome::Rollback(Err(error)),
+					});
+					if paid == Err(Error::<T>::PayoutNotSent.into()) {
+						Self::deposit_event(Event::PayoutFailed { lottery_id, who: winner });
+					}
+				}
+
+				// 2. Updates the progress of the round's payouts, reporting it every 
+				// few payouts. It's not reported for the rounds queued before their
+				// totals were kept
+				if winners.is_empty() {
+					PendingPayouts::<T>::remove(lottery_id, round);
+					PayoutTotals::<T>::remove(lottery_id, round);
+					Self::deposit_event(Event::RoundFullyPaid { lottery_id, round });
+				} else {
+					let total = Self::get_payout_total(lottery_id, round);
+					let paid_so_far = total.saturating_sub(winners.len() as u32);
+					if paid_so_far > 0 && paid_so_far % PAYOUT_PROGRESS_INTERVAL == 0 {
+						Self::deposit_event(Event::PayoutProgress { lottery_id, round, paid_so_far, total });
+					}
+					PendingPayouts::<T>::insert(lottery_id, round, winners);
+				}
+			}
+			consumed
+		}
+
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+
+			// Reports the rounds that have gone undrawn for too long, once each, 
+			// whether or not the automatic draws are enabled, as a missing draw 
+			// trigger is what makes them stale
+			let mut weight = Self::report_stale_rounds(now);
+
+			// Rolls the scratches bought to be rolled on this block, which are
+			// already paid for, whether or not the lotteries are paused
+			weight.saturating_accrue(Self::settle_scratches(now));
+
+			// Enters the subscribers into the new rounds, whether or not the automatic
+			// draws are enabled
+			weight.saturating_accrue(Self::enter_subscribers());
+			let schedule = T::DrawSchedule::get();
+			let disabled = match schedule {
+				DrawSchedule::Blocks(interval) => interval.is_zero(),
+				DrawSchedule::Timestamp { interval_ms } => interval_ms == 0,
+			};
+			if disabled {
+				return weight;
+			}
+			weight.saturating_accrue(T::DbWeight::get().reads(1));
+			if Self::get_paused() || !Self::get_enabled() {
+				return weight;
+			}
+
+			let max_draws = T::MaxAutoDrawsPerBlock::get();
+			let mut draws = 0u32;
+			for lottery_id in Lotteries::<T>::iter_keys() {
+
+				// 1. Checks whether an automatic draw of the lottery is due, stopping 
+				// once the block drew as many lotteries as allowed. The lotteries left 
+				// are still due on the next block, as their draw time isn't recorded
+				if draws >= max_draws {
+					break;
+				}
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				if !Self::draw_due(lottery_id, now, schedule) {
+					continue;
+				}
+				Self::record_draw_time(lottery_id);
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+				// 2. Draws the winner, skipping the draw until the next interval 
+				// if the sales are open or there are not enough participants or funds, 
+				// and leaving the rounds with a committed seed, or drawn by revealing
+				// one, to be revealed. The rounds whose sales closed without enough 
+				// participants are refunded. Either one is charged for the participants 
+				// of the round
+				let participants = Self::participant_count(lottery_id);
+				if Self::sales_closed(lottery_id) && 
+					Self::draw_thresholds_met(lottery_id) &&
+					!SeedCommitments::<T>::contains_key(lottery_id) &&
+					Self::accepts_seed(lottery_id, DrawSeed::OnChain) {
+					let _ = Self::do_award_prize(lottery_id, DrawSeed::OnChain, None);
+					weight.saturating_accrue(T::WeightInfo::award_prize(participants));
+					draws.saturating_inc();
+				} else if Self::refund_if_undersubscribed(lottery_id) {
+					weight.saturating_accrue(T::WeightInfo::cancel_lottery(participants));
+					draws.saturating_inc();
+				}
+			}
+			weight
+		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+
+			// Submits an unsigned draw for each round whose sales are closed, so no
+			// one has to trigger it. The submission is not tied to any key, so every
+			// node running the worker may submit the same draw: the pool keeps only
+			// one of them per round, and each node waits for the unsigned interval 
+			// before submitting it again, in case the first one was never included
+			if !Self::get_enabled() {
+				return;
+			}
+			for lottery_id in Lotteries::<T>::iter_keys() {
+
+				// 1. Checks that the round can be drawn, with enough participants and funds
+				if Self::ensure_can_draw(lottery_id).is_err() || 
+					!Self::draw_thresholds_met(lottery_id) {
+					continue;
+				}
+				let round = Self::get_current_round(lottery_id);
+
+				// 2. Skips the round if this node already submitted its draw recently, 
+				// guarding with the offchain storage shared by the workers of the node
+				let guard = StorageValueRef::persistent(&(b"lottery::draw", lottery_id).encode());
+				let interval = T::UnsignedInterval::get();
+				let submitted = guard.mutate(|last: Result<Option<(u32, BlockNumberFor<T>)>, _>| {
+					match last {
+						Ok(Some((last_round, at))) if last_round == round && 
+							now < at.saturating_add(interval) => Err(()),
+						_ => Ok((round, now)),
+					}
+				});
+				if submitted.is_err() {
+					continue;
+				}
+
+				// 3. Submits the unsigned draw of the round
+				let call = Call::draw_unsigned { lottery_id, round };
+				if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to submit the unsigned draw of lottery {} round {}",
+						lottery_id,
+						round,
+					);
+				}
+			}
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::Migrations::<T>::on_runtime_upgrade()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			migrations::Migrations::<T>::pre_upgrade()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			migrations::Migrations::<T>::post_upgrade(state)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+
+	// The calls made by regular users only act on the account of the signed origin,
+	// so they work the same when dispatched through a proxy of that account
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_lottery())]
+		pub fn create_lottery(
+			origin: OriginFor<T>,
+			config: LotteryConfig<BalanceOf<T>>,
+			randomness: RandomnessSource,
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let creator = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Checks the lottery settings, and that there is room for a new lottery
+			ensure!(!config.ticket_cost.is_zero(), Error::<T>::InvalidTicketCost);
+			ensure!(config.ticket_cost >= T::MinTicketCost::get(), Error::<T>::TicketCostTooLow);
+			ensure!(
+				config.max_participants > 0 && 
+					config.max_participants <= T::MaxParticipants::get(),
+				Error::<T>::InvalidMaxParticipants
+			);
+			let id = Self::get_next_lottery_id();
+			ensure!(id < T::MaxLotteries::get(), Error::<T>::TooManyLotteries);
+
+			// 3. Reserves the creation deposit on the creator's account, so lotteries
+			// can't be created for free
+			let deposit = T::LotteryCreationDeposit::get();
+			if !deposit.is_zero() {
+				T::Currency::reserve_named(&Self::reserve_id(), &creator, deposit)?;
+				CreationDeposits::<T>::insert(id, (&creator, deposit));
+			}
+
+			// 4. Stores the new lottery, ready to sell tickets, along with the source
+			// of the randomness its rounds are drawn with
+			Lotteries::<T>::insert(id, config);
+			LotteryRandomness::<T>::insert(id, randomness);
+			NextLotteryId::<T>::put(id.saturating_add(1));
+
+			// 5. Notify the events
+			Self::deposit_event(Event::LotteryCreated { id });
+			if !deposit.is_zero() {
+				Self::deposit_event(Event::CreationDepositReserved { lottery_id: id, creator, amount: deposit });
+			}
+			Ok(())
+		}
+
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::buy_ticket(T::MaxParticipants::get()))]
+		pub fn buy_ticket(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			quantity: u32,
+		) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature, and that the user is not buying too soon
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			Self::throttle_buy(&buyer)?;
+
+			// 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+			// ignored by the policy is free
+			let bought = Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)?;
+			Ok(Self::purchase_post_info(bought))
+		}
+
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::buy_ticket_for(T::MaxParticipants::get()))]
+		pub fn buy_ticket_for(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			beneficiary: T::AccountId,
+			quantity: u32,
+		) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature, and that the signer is not buying too soon
+			let payer = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			Self::throttle_buy(&payer)?;
+
+			// 2. Buys the tickets for the beneficiary, who is registered as the 
+			// participant and can win the prize, while the signer pays for them. A 
+			// duplicate purchase ignored by the policy is free
+			let bought = Self::do_buy_ticket(&payer, beneficiary, lottery_id, quantity)?;
+			Ok(Self::purchase_post_info(bought))
+		}
+
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::buy_tickets(beneficiaries.len() as u32))]
+		pub fn buy_tickets(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			beneficiaries: BoundedVec<T::AccountId, T::MaxBatch>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature, and that the signer is not buying too 
+			// soon. The whole batch counts as a single purchase
+			let payer = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			Self::throttle_buy(&payer)?;
+
+			// 2. Buys a ticket for each beneficiary, within a storage transaction so
+			// the signer isn't charged for a partial batch if any purchase fails. 
+			// Only the purchases made count, not the ones ignored by the policy
+			let count = with_transaction(|| {
+				let mut count = 0u32;
+				for beneficiary in beneficiaries {
+					match Self::do_buy_ticket(&payer, beneficiary, lottery_id, 1) {
+						Ok(true) => count.saturating_inc(),
+						Ok(false) => {},
+						Err(error) => return TransactionOutcome::Rollback(Err(error)),
+					}
+				}
+				TransactionOutcome::Commit(Ok(count))
+			})?;
+
+			// 3. Notify the event
+			Self::deposit_event(Event::TicketsBought { lottery_id, payer, count });
+			Ok(())
+		}
+
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::buy_ticket_with_referral(T::MaxParticipants::get()))]
+		pub fn buy_ticket_with_referral(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			quantity: u32,
+			referrer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature, that the user is not referring itself,
+			// and that it's not buying too soon
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(referrer != buyer, Error::<T>::SelfReferral);
+			Self::throttle_buy(&buyer)?;
+
+			// 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+			// ignored by the policy is free, and grants no bonus ticket
+			if !Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)? {
+				return Ok(Pays::No.into());
+			}
+
+			// 3. Grants a bonus ticket to the referrer, only once for each account
+			// referred, and only if the referrer participates in the current round
+			// with room left for another ticket. Otherwise the purchase goes on alone.
+			// The bonus ticket counts as sold, as it's refunded like the others
+			if Referrals::<T>::contains_key((&referrer, &buyer)) {
+				return Ok(().into());
+			}
+			let Some(tickets) = Self::tickets_held(lottery_id, &referrer)
+				.filter(|tickets| *tickets < T::MaxTicketsPerAccount::get()) else {
+				return Ok(().into());
+			};
+			Self::set_tickets(lottery_id, &referrer, tickets.saturating_add(1))?;
+			Self::issue_tickets(lottery_id, &referrer, 1)?;
+			TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_add(1));
+			Referrals::<T>::insert((&referrer, &buyer), ());
+
+			// 4. Notify the event
+			Self::deposit_event(Event::ReferralRewarded { lottery_id, referrer, referred: buyer });
+			Ok(().into())
+		}
+
+		// Buys tickets as `buy_ticket` does, attaching a memo to the user's tickets,
+		// which replaces any memo attached before in the round
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::buy_ticket_with_data(T::MaxParticipants::get()))]
+		pub fn buy_ticket_with_data(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			quantity: u32,
+			data: BoundedVec<u8, T::MaxMemo>,
+		) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature, and that the user is not buying too soon
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			Self::throttle_buy(&buyer)?;
+
+			// 2. Buys the tickets for the user, who pays for them. A duplicate purchase
+			// ignored by the policy is free, and keeps the memo attached before
+			if !Self::do_buy_ticket(&buyer, buyer.clone(), lottery_id, quantity)? {
+				return Ok(Pays::No.into());
+			}
+
+			// 3. Attaches the memo to the user's tickets
+			TicketMemos::<T>::insert(lottery_id, &buyer, data);
+			Ok(().into())
+		}
+
+		// Prepays the user's entry into the given number of coming rounds, with a
+		// ticket each. The funds for all the rounds are held up front, priced as 
+		// the first ticket of a round, and each entry pays the price of its ticket
+		// out of them. The subscription ends, refunding the rest, once the funds 
+		// left don't cover an entry
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::subscribe())]
+		pub fn subscribe(origin: OriginFor<T>, lottery_id: LotteryId, rounds: u32) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			let lottery = Self::get_lottery(lottery_id).ok_or(Error::<T>::LotteryNotFound)?;
+
+			// 2. Checks that rounds are being subscribed to, by an account that is
+			// not subscribed yet
+			ensure!(rounds > 0, Error::<T>::InvalidAmount);
+			ensure!(!Subscriptions::<T>::contains_key(lottery_id, &who), Error::<T>::AlreadySubscribed);
+
+			// 3. Holds the funds for every round
+			let funds = Self::tickets_cost(&lottery, 0, 1)?
+				.checked_mul(&rounds.into())
+				.ok_or(Error::<T>::ArithmeticOverflow)?;
+			ensure!(Self::can_afford(&who, funds), Error::<T>::NotEnoughCurrency);
+			Self::hold_ticket_funds(lottery_id, &who, &who, funds)?;
+			Subscriptions::<T>::insert(lottery_id, &who, Subscription { remaining_rounds: rounds, funds });
+
+			// 4. Notify the event
+			Self::deposit_event(Event::Subscribed { lottery_id, who, rounds, funds });
+			Ok(())
+		}
+
+		// Ends the user's subscription, refunding the funds of the rounds it was not
+		// entered into yet. The tickets of the entries already made are kept
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::unsubscribe())]
+		pub fn unsubscribe(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let who = ensure_signed(origin)?;
+
+			// 2. Ends the subscription, and refunds its funds
+			let subscription = Subscriptions::<T>::get(lottery_id, &who).ok_or(Error::<T>::NotSubscribed)?;
+			Self::end_subscription(lottery_id, &who, subscription.funds)
+		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::leave_lottery(T::MaxParticipants::get()))]
+		pub fn leave_lottery(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature, and that the sales are open
+			let who = ensure_signed(origin)?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+			ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+			Self::ensure_sales_open(lottery_id)?;
+
+			// 2. Removes one of the user's tickets, and the user from the participants 
+			// list if it was the last one. A user holding several tickets can leave
+			// partially, or leave completely by calling once per ticket
+			let held_tickets = Self::tickets_held(lottery_id, &who).ok_or(Error::<T>::NotParticipating)?;
+			let remaining_tickets = held_tickets.saturating_sub(1);
+			Self::set_tickets(lottery_id, &who, remaining_tickets)?;
+			if remaining_tickets == 0 {
+				TicketMemos::<T>::remove(lottery_id, &who);
+				#[cfg(feature = "nfts")]
+				TicketItems::<T>::remove(lottery_id, &who);
+			}
+			Self::void_ticket(lottery_id, &who);
+			if Self::participant_count(lottery_id) == 0 {
+				Self::clear_round_end(lottery_id);
+				Self::clear_tickets(lottery_id);
+			}
+
+			// 3. Refunds the ticket by releasing its share of the funds held
+			// for the user's tickets, which may have been bought at different costs
+			let reserved = Self::get_reserved_funds(lottery_id, &who);
+			let refund = if remaining_tickets == 0 {
+				ReservedFunds::<T>::remove(lottery_id, &who);
+				reserved
+			} else {
+				let refund = reserved / held_tickets.into();
+				ReservedFunds::<T>::insert(lottery_id, &who, reserved.saturating_sub(refund));
+				refund
+			};
+			Self::release_ticket_funds(lottery_id, &who, refund)?;
+			TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_sub(1));
+
+			// 4. Notify the event
+			Self::deposit_event(Event::TicketRefunded { lottery_id, who });
+			Ok(())
+		}
+
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::reduce_tickets(T::MaxParticipants::get(), *amount))]
+		pub fn reduce_tickets(origin: OriginFor<T>, lottery_id: LotteryId, amount: u32) -> DispatchResult {
+
+			// 1. Validates the origin signature, and that the sales are open
+			let who = ensure_signed(origin)?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+			ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+			ensure!(amount > 0, Error::<T>::InvalidQuantity);
+			Self::ensure_sales_open(lottery_id)?;
+
+			// 2. Removes the amount of tickets from the user's tickets, and the user 
+			// from the participants list if none are left
+			let held_tickets = Self::tickets_held(lottery_id, &who).ok_or(Error::<T>::NotParticipating)?;
+			ensure!(amount <= held_tickets, Error::<T>::InsufficientTickets);
+			let remaining_tickets = held_tickets - amount;
+			Self::set_tickets(lottery_id, &who, remaining_tickets)?;
+			if remaining_tickets == 0 {
+				TicketMemos::<T>::remove(lottery_id, &who);
+				#[cfg(feature = "nfts")]
+				TicketItems::<T>::remove(lottery_id, &who);
+			}
+			for _ in 0..amount {
+				Self::void_ticket(lottery_id, &who);
+			}
+			if Self::participant_count(lottery_id) == 0 {
+				Self::clear_round_end(lottery_id);
+				Self::clear_tickets(lottery_id);
+			}
+
+			// 3. Refunds the tickets by releasing their share of the funds held
+			// for the user's tickets, which may have been bought at different costs
+			let reserved = Self::get_reserved_funds(lottery_id, &who);
+			let refund = if remaining_tickets == 0 {
+				ReservedFunds::<T>::remove(lottery_id, &who);
+				reserved
+			} else {
+				let refund = (reserved / held_tickets.into()).saturating_mul(amount.into());
+				ReservedFunds::<T>::insert(lottery_id, &who, reserved.saturating_sub(refund));
+				refund
+			};
+			Self::release_ticket_funds(lottery_id, &who, refund)?;
+			TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_sub(amount.into()));
+
+			// 4. Notify the event
+			Self::deposit_event(Event::TicketsReduced { lottery_id, who, amount });
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::draw(T::MaxParticipants::get()))]
+		pub fn draw(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature, any account can trigger the draw
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Checks that the round can be drawn
+			Self::ensure_can_draw(lottery_id)?;
+
+			// 3. Refunds the round if it closed without enough participants, or draws 
+			// the winner and awards the prize
+			if Self::refund_if_undersubscribed(lottery_id) {
+				return Ok(());
+			}
+			Self::do_award_prize(lottery_id, DrawSeed::OnChain, Some(&who))
+		}
+
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::draw_unsigned(T::MaxParticipants::get()))]
+		pub fn draw_unsigned(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			round: u32,
+		) -> DispatchResult {
+
+			// 1. Validates that the transaction is unsigned, as submitted by the 
+			// offchain worker
+			ensure_none(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Checks that the draw is for the current round, and that it can be drawn
+			ensure!(round == Self::get_current_round(lottery_id), Error::<T>::RoundAlreadyDrawn);
+			Self::ensure_can_draw(lottery_id)?;
+
+			// 3. Draws the winner and awards the prize
+			Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::award_prize(T::MaxParticipants::get()))]
+		pub fn award_prize(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature
+			let _who = ensure_root(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Checks that the sales deadline has passed, unless root is allowed 
+			// to draw early, as an escape hatch for the governance
+			if !Self::sales_closed(lottery_id) {
+				ensure!(T::AllowEarlyRootDraw::get(), Error::<T>::DrawNotYetAllowed);
+				if let Some(sales_end) = Self::get_round_end(lottery_id) {
+					let round = Self::get_current_round(lottery_id);
+					Self::deposit_event(Event::EarlyDrawForced { lottery_id, round, sales_end });
+				}
+			}
+
+			// 3. Draws the winner and awards the prize immediately. The lotteries
+			// drawn by commit and reveal still require the seed
+			let participants = Self::participant_count(lottery_id);
+			Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)?;
+
+			// 4. Charges the weight of the participants actually drawn over, and
+			// nothing at all when there were none and the round was only rolled over
+			let actual_weight = Some(T::WeightInfo::award_prize(participants));
+			if participants == 0 {
+				return Ok(PostDispatchInfo { actual_weight, pays_fee: Pays::No });
+			}
+			Ok(actual_weight.into())
+		}
+
+		// Draws a round left undrawn for more than the stale threshold past the end 
+		// of its ticket sales, or past its start when the sales have no end, as when
+		// the chain halted over its scheduled or automatic draw. It's the recovery 
+		// for the rounds reported stale, and can't draw the rounds whose deadline
+		// passed more recently. A round without enough participants to be drawn 
+		// is refunded instead
+		#[pallet::call_index(38)]
+		#[pallet::weight(
+			T::WeightInfo::force_settle_stale_round(T::MaxParticipants::get())
+				.max(T::WeightInfo::cancel_lottery(T::MaxParticipants::get()))
+		)]
+		pub fn force_settle_stale_round(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature
+			ensure_root(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+			ensure!(!Self::get_round_drawn(lottery_id), Error::<T>::RoundAlreadyDrawn);
+
+			// 2. Checks that the round is stale
+			let threshold = T::StaleRoundBlocks::get();
+			let since = Self::get_round_end(lottery_id)
+				.or_else(|| Self::get_round_start(lottery_id))
+				.ok_or(Error::<T>::RoundNotStale)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				!threshold.is_zero() && now.saturating_sub(since) > threshold,
+				Error::<T>::RoundNotStale
+			);
+
+			// 3. Draws the winner and awards the prize immediately, as the normal 
+			// draw would, or refunds the participants when there are too few of them
+			let round = Self::get_current_round(lottery_id);
+			let participants = Self::participant_count(lottery_id);
+			let actual_weight = if participants > 0 && participants < T::MinParticipants::get() {
+				let refunded = Self::refund_round(lottery_id);
+				Self::deposit_event(Event::RoundRefundedInsufficientParticipants { lottery_id, round, refunded });
+				T::WeightInfo::cancel_lottery(participants)
+			} else {
+				Self::do_award_prize(lottery_id, DrawSeed::OnChain, None)?;
+				T::WeightInfo::force_settle_stale_round(participants)
+			};
+
+			// 4. Notify the event
+			Self::deposit_event(Event::StaleRoundSettled { lottery_id, round });
+			Ok(Some(actual_weight).into())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_ticket_cost())]
+		pub fn set_ticket_cost(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			new_cost: BalanceOf<T>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Checks that the new cost is not zero nor below the minimum
+			ensure!(!new_cost.is_zero(), Error::<T>::InvalidTicketCost);
+			ensure!(new_cost >= T::MinTicketCost::get(), Error::<T>::TicketCostTooLow);
+
+			// 3. Stores the new cost, to be charged from now on
+			let old = Lotteries::<T>::try_mutate(lottery_id, |lottery| {
+				let lottery = lottery.as_mut().ok_or(Error::<T>::LotteryNotFound)?;
+				Ok::<_, Error<T>>(mem::replace(&mut lottery.ticket_cost, new_cost))
+			})?;
+
+			// 4. Notify the event
+			Self::deposit_event(Event::TicketCostChanged { lottery_id, old, new: new_cost });
+			Ok(())
+		}
+
+		// Sets what the prize of the lottery's current round is made of, before any
+		// ticket is sold so the participants know what they enter for. A fixed 
+		// prize is to be funded with donations before the round is drawn. The round
+		// after it goes back to the pool
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_prize_source())]
+		pub fn set_prize_source(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			source: PrizeSource<BalanceOf<T>>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Checks that the round has not started, and that a fixed prize is 
+			// worth winning
+			ensure!(Self::participant_count(lottery_id) == 0, Error::<T>::RoundAlreadyStarted);
+			if let PrizeSource::Fixed { amount } = source {
+				ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			}
+
+			// 3. Stores the source for the current round
+			RoundPrizeSource::<T>::insert(lottery_id, source);
+
+			// 4. Notify the event
+			let round = Self::get_current_round(lottery_id);
+			Self::deposit_event(Event::PrizeSourceSet { lottery_id, round, source });
+			Ok(())
+		}
+
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_max_participants())]
+		pub fn set_max_participants(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			new_cap: u32,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Checks that the new cap is within the bound of the participants list,
+			// and that it still fits the participants of the current round
+			ensure!(
+				new_cap > 0 && new_cap <= T::MaxParticipants::get(),
+				Error::<T>::InvalidMaxParticipants
+			);
+			ensure!(new_cap >= Self::participant_count(lottery_id), Error::<T>::CapBelowCurrent);
+
+			// 3. Stores the new cap, to be checked on the next purchases
+			let old = Lotteries::<T>::try_mutate(lottery_id, |lottery| {
+				let lottery = lottery.as_mut().ok_or(Error::<T>::LotteryNotFound)?;
+				Ok::<_, Error<T>>(mem::replace(&mut lottery.max_participants, new_cap))
+			})?;
+
+			// 4. Notify the event
+			Self::deposit_event(Event::MaxParticipantsChanged { lottery_id, old, new: new_cap });
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_lottery(T::MaxParticipants::get()))]
+		pub fn cancel_lottery(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let _who = ensure_root(origin)?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Refunds every participant, and gets ready for another lottery round
+			let refunded = Self::refund_round(lottery_id);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::LotteryCancelled { lottery_id, refunded });
+
+			// 4. Slashes the creation deposit, if the lottery was never drawn
+			Self::slash_creation_deposit(lottery_id)
+		}
+
+		// Cancels the current round as `cancel_lottery` does, refunding up to the
+		// given number of participants on each call, so the rounds with more 
+		// participants than fit a block can be cancelled. The round is only reset 
+		// once every participant is refunded, and can't be drawn nor sell tickets
+		// in the meantime
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::cancel_lottery_paged((*limit).min(T::MaxParticipants::get())))]
+		pub fn cancel_lottery_paged(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+
+			// 1. Validates the origin signature
+			ensure_root(origin)?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Refunds the participants of the page, taken from the end of the list
+			let page = Self::take_last_participants(lottery_id, limit);
+			let (mut refunded, mut missing) = CancellationProgress::<T>::take(lottery_id).unwrap_or_default();
+			Self::refund_participants(lottery_id, &page, &mut refunded, &mut missing);
+			let actual_weight = Some(T::WeightInfo::cancel_lottery_paged(page.len() as u32));
+
+			// 3. Keeps track of the progress while participants remain to be refunded,
+			// forgetting the tickets of the participants refunded
+			let remaining = Self::participant_count(lottery_id);
+			if remaining > 0 {
+				for (who, _) in page.iter() {
+					Self::void_tickets(lottery_id, who);
+					TicketMemos::<T>::remove(lottery_id, who);
+					#[cfg(feature = "nfts")]
+					TicketItems::<T>::remove(lottery_id, who);
+				}
+				CancellationProgress::<T>::insert(lottery_id, (refunded, missing));
+				Self::deposit_event(Event::CancellationProgressed { lottery_id, refunded, remaining });
+				return Ok(actual_weight.into());
+			}
+
+			// 4. Gets ready for another lottery round once everyone is refunded
+			Self::close_refunded_round(lottery_id, missing);
+			Self::deposit_event(Event::LotteryCancelled { lottery_id, refunded });
+
+			// 5. Slashes the creation deposit, if the lottery was never drawn
+			Self::slash_creation_deposit(lottery_id)?;
+			Ok(actual_weight.into())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::add_to_allowlist())]
+		pub fn add_to_allowlist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Allows the account to buy tickets
+			if !AllowList::<T>::contains_key(&who) {
+				AllowList::<T>::insert(&who, ());
+				AllowListCount::<T>::mutate(|count| count.saturating_inc());
+			}
+
+			// 3. Notify the event
+			Self::deposit_event(Event::AddedToAllowList { who });
+			Ok(())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::remove_from_allowlist())]
+		pub fn remove_from_allowlist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Stops the account from buying new tickets. Tickets already 
+			// bought are kept until the round is over
+			if AllowList::<T>::take(&who).is_some() {
+				AllowListCount::<T>::mutate(|count| count.saturating_dec());
+			}
+
+			// 3. Notify the event
+			Self::deposit_event(Event::RemovedFromAllowList { who });
+			Ok(())
+		}
+
+		// Allows every account of the batch to buy tickets. The accounts already
+		// in the allowlist are skipped
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::add_to_allowlist_batch(who.len() as u32))]
+		pub fn add_to_allowlist_batch(
+			origin: OriginFor<T>,
+			who: BoundedVec<T::AccountId, T::MaxBatch>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Allows each account to buy tickets
+			let mut added = 0u32;
+			for account in who.iter() {
+				if !AllowList::<T>::contains_key(account) {
+					AllowList::<T>::insert(account, ());
+					added.saturating_inc();
+				}
+			}
+			AllowListCount::<T>::mutate(|count| *count = count.saturating_add(added));
+
+			// 3. Notify the event
+			Self::deposit_event(Event::AllowListUpdated { added, removed: 0 });
+			Ok(())
+		}
+
+		// Stops every account of the batch from buying new tickets. The accounts 
+		// not in the allowlist are skipped
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::remove_from_allowlist_batch(who.len() as u32))]
+		pub fn remove_from_allowlist_batch(
+			origin: OriginFor<T>,
+			who: BoundedVec<T::AccountId, T::MaxBatch>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Removes each account, keeping the tickets already bought until 
+			// the round is over
+			let mut removed = 0u32;
+			for account in who.iter() {
+				if AllowList::<T>::take(account).is_some() {
+					removed.saturating_inc();
+				}
+			}
+			AllowListCount::<T>::mutate(|count| *count = count.saturating_sub(removed));
+
+			// 3. Notify the event
+			Self::deposit_event(Event::AllowListUpdated { added: 0, removed });
+			Ok(())
+		}
+
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::add_fee_exempt())]
+		pub fn add_fee_exempt(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Exempts the account from the fee on the prizes it wins
+			FeeExempt::<T>::insert(&who, ());
+
+			// 3. Notify the event
+			Self::deposit_event(Event::FeeExemptAdded { who });
+			Ok(())
+		}
+
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::remove_fee_exempt())]
+		pub fn remove_fee_exempt(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Charges the fee again on the next prizes the account wins
+			FeeExempt::<T>::remove(&who);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::FeeExemptRemoved { who });
+			Ok(())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::commit_seed())]
+		pub fn commit_seed(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			hash: [u8; 32],
+		) -> DispatchResult {
+
+			// 1. Validates the origin is the oracle
+			T::SeedOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Checks that the current round has no seed yet
+			ensure!(
+				!SeedCommitments::<T>::contains_key(lottery_id),
+				Error::<T>::SeedAlreadyCommitted
+			);
+
+			// 3. Stores the hash of the seed, so the round can only be drawn 
+			// by revealing it
+			SeedCommitments::<T>::insert(lottery_id, hash);
+
+			// 4. Notify the event
+			let round = Self::get_current_round(lottery_id);
+			Self::deposit_event(Event::SeedCommitted { lottery_id, round });
+			Ok(())
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::reveal_and_draw(T::MaxParticipants::get()))]
+		pub fn reveal_and_draw(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			seed: [u8; 32],
+		) -> DispatchResult {
+
+			// 1. Validates the origin is the oracle
+			T::SeedOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Checks that the seed matches the hash committed for the round
+			let commitment = Self::get_seed_commitment(lottery_id)
+				.ok_or(Error::<T>::SeedNotCommitted)?;
+			ensure!(blake2_256(&seed) == commitment, Error::<T>::InvalidSeed);
+
+			// 3. Checks that the sales deadline has passed
+			ensure!(
+				Self::get_round_end(lottery_id).is_some() && Self::sales_closed(lottery_id),
+				Error::<T>::DrawNotYetAllowed
+			);
+
+			// 4. Notify the event
+			let round = Self::get_current_round(lottery_id);
+			Self::deposit_event(Event::SeedRevealed { lottery_id, round, seed });
+
+			// 5. Draws the winner mixing the seed with the on-chain randomness, 
+			// and awards the prize
+			Self::do_award_prize(lottery_id, DrawSeed::Revealed(seed), None)
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::donate())]
+		pub fn donate(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Checks that something is being donated, within the funds the 
+			// lottery can hold
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			Self::ensure_escrow_room(lottery_id, amount)?;
+
+			// 3. Transfers the donation to the lottery's account, to be included 
+			// in the next prize. The donor is not registered as a participant
+			Self::deposit_to_pot(lottery_id, &who, amount)?;
+			JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(amount));
+
+			// 4. Notify the event
+			Self::deposit_event(Event::Donated { lottery_id, who, amount });
+			Ok(())
+		}
+
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::scratch())]
+		pub fn scratch(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+			ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+
+			// 2. Checks that the randomness is old enough, and that the lottery's
+			// account can pay the scratch prize without touching the prizes owed 
+			// to the winners of previous rounds
+			ensure!(Self::randomness_ready(DrawSeed::OnChain), Error::<T>::RandomnessNotReady);
+			let prize = T::ScratchPrize::get();
+			ensure!(Self::available_pot(lottery_id) >= prize, Error::<T>::PotTooSmall);
+
+			// 3. Queues the scratch to be rolled on a later block, at least the
+			// randomness delay after this one, as the randomness known on this block
+			// would let the user check the outcome before buying it
+			let settles_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::RandomnessDelay::get())
+				.saturating_add(1u32.into());
+			PendingScratches::<T>::try_mutate(settles_at, |pending| pending.try_push((lottery_id, who.clone())))
+				.map_err(|_| Error::<T>::TooManyScratches)?;
+
+			// 4. Charges the scratch, which is independent of the round and
+			// doesn't make the user a participant. Its cost is added to the pot
+			let cost = T::ScratchCost::get();
+			Self::deposit_to_pot(lottery_id, &who, cost)?;
+			JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(cost));
+
+			// 5. Notify the event
+			Self::deposit_event(Event::ScratchBought { lottery_id, who, settles_at });
+			Ok(())
+		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::claim_prize())]
+		pub fn claim_prize(origin: OriginFor<T>, lottery_id: LotteryId) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let winner = ensure_signed(origin)?;
+
+			// 2. Pays out the prize owed to the winner, without waiting for the 
+			// payouts done on idle blocks
+			Self::do_claim_prize(lottery_id, winner)
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::sweep_expired(*max_prizes))]
+		pub fn sweep_expired(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			max_prizes: u32,
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature, any account can sweep the prizes
+			let _who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Looks for the expired prizes among the given number of unclaimed prizes
+			let expired: Vec<_> = UnclaimedPrizes::<T>::iter_prefix(lottery_id)
+				.take(max_prizes as usize)
+				.filter(|(_, (_, drawn_at))| Self::prize_expired(*drawn_at))
+				.map(|(who, (amount, _))| (who, amount))
+				.collect();
+
+			// 3. Moves the expired prizes back into the pot, as a jackpot carried 
+			// over to the next round
+			for (who, amount) in expired {
+				Self::expire_prize(lottery_id, who, amount);
+			}
+			Ok(())
+		}
+
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Stops buying and leaving tickets, and drawing the prizes. The 
+			// lotteries can still be cancelled and the prizes claimed, so the 
+			// funds can always be returned
+			Paused::<T>::put(true);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::LotteryPaused);
+			Ok(())
+		}
+
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Resumes the lotteries
+			Paused::<T>::put(false);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::LotteryUnpaused);
+			Ok(())
+		}
+
+		// Turns the lotteries on, which the other calls require
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::enable_lottery())]
+		pub fn enable_lottery(origin: OriginFor<T>) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// 2. Turns the lotteries on
+			Enabled::<T>::put(true);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::LotteryEnabled);
+			Ok(())
+		}
+
+		// Turns the lotteries off, leaving every call but enabling them unavailable,
+		// except the ones giving funds back: leaving the rounds, reducing tickets, 
+		// unsubscribing, cancelling the lotteries and claiming the prizes. Unlike a
+		// pause, it's meant for the deployment before any lottery runs, but those 
+		// calls keep the funds of any round in progress from being locked
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::disable_lottery())]
+		pub fn disable_lottery(origin: OriginFor<T>) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// 2. Turns the lotteries off
+			Enabled::<T>::put(false);
+
+			// 3. Notify the event
+			Self::deposit_event(Event::LotteryDisabled);
+			Ok(())
+		}
+
+		// Registers the account with a single ticket without charging it, as an escape 
+		// hatch for support cases such as a purchase paid but not registered
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::force_add_participant(T::MaxParticipants::get()))]
+		pub fn force_add_participant(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			who: T::AccountId,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			let lottery = Self::get_lottery(lottery_id).ok_or(Error::<T>::LotteryNotFound)?;
+			ensure!(
+				who != Self::get_pallet_account(lottery_id),
+				Error::<T>::InvalidParticipant
+			);
+
+			// 2. Registers the account as a new participant, within the participants cap
+			ensure!(
+				Self::tickets_held(lottery_id, &who).is_none(),
+				Error::<T>::AccountAlreadyParticipating
+			);
+			let count = Self::participant_count(lottery_id);
+			ensure!(count < lottery.max_participants, Error::<T>::LotteryFull);
+			let round_started = count == 0;
+			if round_started {
+				Self::start_sales(lottery_id);
+			}
+			Self::set_tickets(lottery_id, &who, 1)?;
+			Self::issue_tickets(lottery_id, &who, 1)?;
+			log::info!(target: LOG_TARGET, "Force added {:?} to lottery {}", who, lottery_id);
+
+			// 3. Notify the events
+			if round_started {
+				let round = Self::get_current_round(lottery_id);
+				Self::deposit_event(Event::RoundStarted { lottery_id, round });
+			}
+			Self::deposit_event(Event::ParticipantForceAdded { lottery_id, who });
+			Ok(())
+		}
+
+		// Removes the participant and all of its tickets without refunding them, as 
+		// an escape hatch for support cases. The funds held for its tickets are
+		// collected into the pot
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::force_remove_participant(T::MaxParticipants::get()))]
+		pub fn force_remove_participant(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			who: T::AccountId,
+		) -> DispatchResult {
+
+			// 1. Validates the origin is allowed to manage the lottery
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Removes the participant along with its tickets
+			ensure!(Self::tickets_held(lottery_id, &who).is_some(), Error::<T>::NotParticipating);
+			Self::set_tickets(lottery_id, &who, 0)?;
+			if Self::participant_count(lottery_id) == 0 {
+				Self::clear_round_end(lottery_id);
+				Self::clear_tickets(lottery_id);
+			} else {
+				Self::void_tickets(lottery_id, &who);
+			}
+			TicketMemos::<T>::remove(lottery_id, &who);
+			#[cfg(feature = "nfts")]
+			TicketItems::<T>::remove(lottery_id, &who);
+
+			// 3. Collects the funds held for its tickets into the pot
+			let held = ReservedFunds::<T>::take(lottery_id, &who);
+			let collected = Self::collect_ticket_funds(lottery_id, &who, held)?;
+			log::info!(
+				target: LOG_TARGET,
+				"Force removed {:?} from lottery {}, collecting {:?} into the pot",
+				who,
+				lottery_id,
+				collected,
+			);
+
+			// 4. Notify the event
+			Self::deposit_event(Event::ParticipantForceRemoved { lottery_id, who });
+			Ok(())
+		}
+
+		// Resets the nonce of the random numbers. The nonce only keeps consecutive
+		// subjects apart within a block, and is combined with the parent block hash 
+		// and the randomness module's entropy, so starting it over doesn't make the 
+		// random numbers any more predictable
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::reset_nonce())]
+		pub fn reset_nonce(origin: OriginFor<T>) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			ensure_root(origin)?;
+			Self::ensure_enabled()?;
+
+			// 2. Starts the nonce over
+			let previous = Nonce::<T>::take();
+
+			// 3. Notify the event
+			Self::deposit_event(Event::NonceReset { previous });
+			Ok(())
+		}
+
+		// Recovers funds stuck in the lottery's account, such as funds transferred
+		// to it by hand or left behind by a failed migration. Up to the given amount
+		// is sent to the destination, never touching the funds carried over, owed
+		// to the winners or held for the tickets of the current round. It works
+		// while the lotteries are disabled too, as that's when stuck funds are
+		// most likely to be recovered
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::emergency_withdraw())]
+		pub fn emergency_withdraw(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			dest: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			ensure_root(origin)?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Caps the amount at the funds not owed to anyone
+			let stray = Self::pot_balance(lottery_id).saturating_sub(Self::owed_funds(lottery_id));
+			let amount = amount.min(stray);
+			ensure!(!amount.is_zero(), Error::<T>::NoStrayFunds);
+
+			// 3. Sends the funds, keeping the lottery's account alive while it holds 
+			// owed funds
+			Self::pay_out(lottery_id, &dest, amount)?;
+
+			// 4. Notify the event
+			Self::deposit_event(Event::EmergencyWithdrawal { lottery_id, dest, amount });
+			Ok(())
+		}
+
+		// Draws the round using the given seed instead of the randomness module, so
+		// integration tests can assert the winners. Only compiled with the 
+		// `test-helpers` feature, which can't be enabled on a Wasm runtime
+		#[cfg(feature = "test-helpers")]
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::award_prize(T::MaxParticipants::get()))]
+		pub fn force_draw_with_seed(
+			origin: OriginFor<T>,
+			lottery_id: LotteryId,
+			seed: [u8; 32],
+		) -> DispatchResult {
+
+			// 1. Validates the origin signature
+			let _who = ensure_root(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+
+			// 2. Draws the winner from the seed and awards the prize
+			Self::do_award_prize(lottery_id, DrawSeed::Fixed(seed), None)
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		// Only the unsigned draws of rounds that can currently be drawn are valid. 
+		// Without a signature nobody pays for them, so spam is kept out by making
+		// a single draw valid per round: every copy provides the same tag, so the
+		// pool keeps only one of them, and the ones left over become stale once 
+		// the round is drawn. Rounds waiting for a seed can't be drawn this way,
+		// and the on-chain randomness remains as predictable as on the signed draw
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::draw_unsigned { lottery_id, round } = call else {
+				return InvalidTransaction::Call.into();
+			};
+			if *round != Self::get_current_round(lottery_id) {
+				return InvalidTransaction::Stale.into();
+			}
+			if !Self::get_enabled() || 
+				Self::ensure_can_draw(*lottery_id).is_err() ||
+				!Self::draw_thresholds_met(*lottery_id) {
+				return InvalidTransaction::Call.into();
+			}
+			ValidTransaction::with_tag_prefix("LotteryDraw")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((lottery_id, round))
+				.longevity(T::UnsignedInterval::get().saturated_into::<u64>())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+
+		/// Returns the number of accounts currently participating in the lottery
+		pub fn participant_count(lottery_id: LotteryId) -> u32 {
+			Self::count_participants(lottery_id)
+		}
+
+		/// Returns the number of tickets the account holds in the current round of 
+		/// the lottery, which is zero if it's not participating. It's read from the 
+		/// account's ticket numbers alone, without going through the participants
+		pub fn tickets_of(lottery_id: LotteryId, who: &T::AccountId) -> u32 {
+			TicketNumbers::<T>::decode_len(lottery_id, who).unwrap_or(0) as u32
+		}
+
+		/// Returns the block the current round of the lottery is expected to be drawn at,
+		/// or `None` if it has no participants or no draw is expected at a given block.
+		/// With automatic draws in blocks, that's the first automatic draw once the 
+		/// sales are closed. Otherwise, it's the end of the sales, when the round can 
+		/// be drawn by any account, if they have an end
+		pub fn next_draw_at(lottery_id: LotteryId) -> Option<BlockNumberFor<T>> {
+			if Self::participant_count(lottery_id) == 0 {
+				return None;
+			}
+			let sales_end = Self::get_round_end(lottery_id);
+			match T::DrawSchedule::get() {
+				DrawSchedule::Blocks(interval) if !interval.is_zero() => {
+					let next = Self::get_last_draw(lottery_id).saturating_add(interval);
+					let Some(end) = sales_end.filter(|end| *end > next) else {
+						return Some(next);
+					};
+					let intervals = end
+						.saturating_sub(next)
+						.saturating_add(interval.saturating_sub(1u32.into())) / interval;
+					Some(next.saturating_add(intervals.saturating_mul(interval)))
+				},
+				_ => sales_end,
+			}
+		}
+
+		/// Returns the hash of the participants a round of the lottery was drawn over,
+		/// if the round is in the winners history.
+		///
+		/// It's the blake2_256 hash of the SCALE encoded accounts of the participants,
+		/// sorted by their encoded bytes and concatenated, with no length prefix and
+		/// no separator, whatever the hashing of the runtime
+		pub fn round_participants_hash(lottery_id: LotteryId, round: u32) -> Option<[u8; 32]> {
+			Self::get_winner_history(lottery_id)
+				.iter()
+				.find(|record| record.round == round)
+				.map(|record| record.participants_hash)
+		}
+
+		/// Returns a page of the winners history of the lottery, newest first, as 
+		/// (round, winner, prize), skipping the `start` most recent winners. At most
+		/// `MAX_HISTORY_PAGE` winners are returned, and none past the oldest one
+		pub fn winner_history(
+			lottery_id: LotteryId,
+			start: u32,
+			limit: u32,
+		) -> Vec<(u32, T::AccountId, BalanceOf<T>)> {
+			Self::get_winner_history(lottery_id)
+				.into_iter()
+				.rev()
+				.skip(start as usize)
+				.take(limit.min(MAX_HISTORY_PAGE) as usize)
+				.map(|record| (record.round, record.winner, record.prize))
+				.collect()
+		}
+
+		/// Returns the share of the tickets of the current round held by the account, 
+		/// which is zero if the account is not participating or the round is empty
+		pub fn win_probability(lottery_id: LotteryId, who: &T::AccountId) -> Perbill {
+			let tickets = Self::tickets_held(lottery_id, who).unwrap_or(0);
+			let total_tickets = Self::round_tickets(lottery_id);
+			if total_tickets == 0 {
+				return Perbill::zero();
+			}
+			Perbill::from_rational(tickets, total_tickets)
+		}
+
+		/// Maps the random seed to an index lower than the number of participants,
+		/// or zero when there are none. The first 8 bytes of the seed are read as a
+		/// little-endian u64, scaled down to the participants by keeping the top 64
+		/// bits of its product with their number. Each index is picked by as many
+		/// values of the u64, give or take one, so no index is favored by more than
+		/// one part in 2^32 whatever the number of participants, and the selection
+		/// never has to draw again. A seed too short to read the number from is
+		/// hashed first. Doesn't access the storage, so the selection can be checked
+		/// on its own
+		pub fn select_winner_index(seed: &[u8], participant_count: u32) -> u32 {
+			if participant_count == 0 {
+				return 0;
+			}
+			let hashed;
+			let bytes = if seed.len() < mem::size_of::<u64>() {
+				hashed = blake2_256(seed);
+				&hashed[..]
+			} else {
+				seed
+			};
+			let mut word = [0u8; 8];
+			word.copy_from_slice(&bytes[..mem::size_of::<u64>()]);
+			let number = u64::from_le_bytes(word);
+			((number as u128 * participant_count as u128) >> 64) as u32
+		}
+
+		/// Maps the random seed to distinct indices lower than the number of 
+		/// participants, up to the given number of winners, by a partial Fisher-Yates
+		/// shuffle. Each position of the shuffle is picked with `select_winner_index`
+		/// from the seed hashed along with the position, and only the indices moved
+		/// by the shuffle are kept, so the indices are produced one at a time without
+		/// holding every participant. Doesn't access the storage either
+		pub fn select_winner_indices(
+			seed: &[u8],
+			participant_count: u32,
+			winners: u32,
+		) -> impl Iterator<Item = u32> + '_ {
+			let draws = winners.min(participant_count);
+			(0..draws).scan(BTreeMap::<u32, u32>::new(), move |moved, position| {
+				let subseed = blake2_256(&(seed, position).encode());
+				let picked = position + Self::select_winner_index(&subseed, participant_count - position);
+				let index = moved.get(&picked).copied().unwrap_or(picked);
+				let replaced = moved.get(&position).copied().unwrap_or(position);
+				moved.insert(picked, replaced);
+				Some(index)
+			})
+		}
+
+		/// Returns the prize the current round would award, before the cap and the fee, 
+		/// made of the funds in the lottery's account and the ticket funds still held
+		pub fn current_pot(lottery_id: LotteryId) -> BalanceOf<T> {
+			Self::available_pot(lottery_id).saturating_add(Self::uncollected_ticket_funds(lottery_id))
+		}
+
+		/// Returns the state of the current round of the lottery, if the lottery exists
+		pub fn current_round_info(
+			lottery_id: LotteryId,
+		) -> Option<RoundInfo<BalanceOf<T>, BlockNumberFor<T>>> {
+			let lottery = Self::get_lottery(lottery_id)?;
+			Some(RoundInfo {
+				round: Self::get_current_round(lottery_id),
+				participant_count: Self::participant_count(lottery_id),
+				pot: Self::current_pot(lottery_id),
+				ticket_cost: Self::tickets_cost(&lottery, Self::round_tickets(lottery_id), 1)
+					.unwrap_or(lottery.ticket_cost),
+				ends_at: Self::get_round_end(lottery_id),
+				paused: Self::get_paused(),
+			})
+		}
+
+		// Refunds every participant of the current round by releasing its funds, and
+		// gets ready for another round, keeping the jackpot carried over. A single 
+		// refund falling short, such as when the reserved funds were slashed in the
+		// meantime, doesn't abort the others. The participants already refunded by a 
+		// paged cancellation are accounted for. Returns the participants refunded
+		fn refund_round(lottery_id: LotteryId) -> u32 {
+			let participants = Self::participants_of(lottery_id);
+			let (mut refunded, mut missing) = CancellationProgress::<T>::take(lottery_id).unwrap_or_default();
+			Self::refund_participants(lottery_id, &participants, &mut refunded, &mut missing);
+			Self::close_refunded_round(lottery_id, missing);
+			refunded
+		}
+
+		// Refunds each of the participants, adding up the participants refunded and
+		// the funds that could not be refunded, and takes their tickets out of the
+		// tickets sold, even the ones whose refund fell short
+		fn refund_participants(
+			lottery_id: LotteryId,
+			participants: &[(T::AccountId, u32)],
+			refunded: &mut u32,
+			missing: &mut BalanceOf<T>,
+		) {
+			for (who, _) in participants.iter() {
+				let refund = ReservedFunds::<T>::take(lottery_id, who);
+				let shortfall = Self::release_ticket_funds(lottery_id, who, refund)
+					.unwrap_or(refund);
+				if shortfall.is_zero() {
+					*refunded += 1;
+				} else {
+					*missing = missing.saturating_add(shortfall);
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to refund {:?} of {:?} to {:?} in lottery {}",
+						shortfall,
+						refund,
+						who,
+						lottery_id,
+					);
+				}
+			}
+			let tickets = Self::total_tickets(participants);
+			TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_sub(tickets.into()));
+		}
+
+		// Reports the funds that could not be refunded to the participants of the 
+		// round, and resets the participants list to get ready for another round
+		fn close_refunded_round(lottery_id: LotteryId, missing: BalanceOf<T>) {
+			if !missing.is_zero() {
+				Self::deposit_event(Event::RefundShortfall { lottery_id, missing });
+			}
+			Self::clear_participants(lottery_id);
+			Self::clear_round_end(lottery_id);
+			SeedCommitments::<T>::remove(lottery_id);
+			RoundPrizeSource::<T>::remove(lottery_id);
+			CurrentRound::<T>::mutate(lottery_id, |round| *round = round.saturating_add(1));
+			Self::clear_tickets(lottery_id);
+			#[cfg(feature = "nfts")]
+			let _ = TicketItems::<T>::clear_prefix(lottery_id, T::MaxParticipants::get(), None);
+		}
+
+		// Slashes the creation deposit of the lottery to the fee destination, which 
+		// is only still held when the lottery was never drawn
+		fn slash_creation_deposit(lottery_id: LotteryId) -> DispatchResult {
+			if let Some((creator, deposit)) = CreationDeposits::<T>::take(lottery_id) {
+				let missing = T::Currency::repatriate_reserved_named(
+					&Self::reserve_id(),
+					&creator, 
+					&T::FeeDestination::get(), 
+					deposit, 
+					BalanceStatus::Free)?;
+				let amount = deposit.saturating_sub(missing);
+				Self::deposit_event(Event::CreationDepositSlashed { lottery_id, creator, amount });
+			}
+			Ok(())
+		}
+
+		// Enters the subscribers of each lottery into its current round, up to a 
+		// bounded number of subscribers per block, carrying on from the last one 
+		// entered on the next block. Each round is only gone through once
+		fn enter_subscribers() -> Weight {
+			let mut weight = T::DbWeight::get().reads(2);
+			if Self::get_paused() || !Self::get_enabled() {
+				return weight;
+			}
+			let mut budget = T::MaxAutoEntriesPerBlock::get();
+			for lottery_id in Lotteries::<T>::iter_keys() {
+				if budget == 0 {
+					break;
+				}
+
+				// 1. Skips the rounds whose subscribers are all entered, or that are
+				// being cancelled
+				weight.saturating_accrue(T::DbWeight::get().reads(4));
+				let round = Self::get_current_round(lottery_id);
+				if Self::get_auto_entered_round(lottery_id) == Some(round) || 
+					CancellationProgress::<T>::contains_key(lottery_id) {
+					continue;
+				}
+
+				// 2. Gathers the subscribers to enter on this block, after the last 
+				// one entered into the round
+				let subscribers = match Self::get_auto_entry_cursor(lottery_id) {
+					Some((cursor_round, last)) if cursor_round == round => Subscriptions::<T>::iter_prefix_from(
+						lottery_id, 
+						Subscriptions::<T>::hashed_key_for(lottery_id, &last)),
+					_ => Subscriptions::<T>::iter_prefix(lottery_id),
+				};
+				let mut subscribers = subscribers.peekable();
+				let mut batch = Vec::new();
+				while budget > 0 {
+					let Some(subscriber) = subscribers.next() else {
+						break;
+					};
+					budget -= 1;
+					batch.push(subscriber);
+				}
+				let exhausted = subscribers.peek().is_none();
+
+				// 3. Enters each of them, and keeps track of the progress
+				for (who, subscription) in batch.iter() {
+					Self::auto_enter(lottery_id, round, who, subscription.clone());
+					weight.saturating_accrue(T::WeightInfo::buy_ticket(T::MaxParticipants::get()));
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				}
+				if exhausted {
+					AutoEnteredRound::<T>::insert(lottery_id, round);
+					AutoEntryCursor::<T>::remove(lottery_id);
+				} else if let Some((last, _)) = batch.pop() {
+					AutoEntryCursor::<T>::insert(lottery_id, (round, last));
+				}
+				weight.saturating_accrue(T::DbWeight::get().writes(2));
+			}
+			weight
+		}
+
+		// Buys a ticket of the round for the subscriber, out of the subscription's 
+		// funds. An entry that fails, such as when the round is full, is rolled back
+		// and skipped, keeping the round for a later one
+		fn auto_enter(
+			lottery_id: LotteryId,
+			round: u32,
+			who: &T::AccountId,
+			mut subscription: Subscription<BalanceOf<T>>,
+		) {
+
+			// 1. Prices the ticket, ending the subscription if the funds left fall short
+			let Some(lottery) = Self::get_lottery(lottery_id) else {
+				return;
+			};
+			let Ok(price) = Self::tickets_cost(&lottery, Self::round_tickets(lottery_id), 1) else {
+				return;
+			};
+			if price > subscription.funds {
+				let _ = Self::end_subscription(lottery_id, who, subscription.funds);
+				return;
+			}
+
+			// 2. Releases the price out of the subscription's funds, and buys the 
+			// ticket with it as on any purchase
+			let entered = with_transaction(|| {
+				let bought = Self::release_ticket_funds(lottery_id, who, price)
+					.and_then(|_| Self::do_buy_ticket(who, who.clone(), lottery_id, 1));
+				match bought {
+					Ok(true) => TransactionOutcome::Commit(Ok(())),
+					Ok(false) => TransactionOutcome::Rollback(Err(DispatchError::Other("Entry ignored"))),
+					Err(error) => TransactionOutcome::Rollback(Err(error)),
+				}
+			});
+			if let Err(error) = entered {
+				log::debug!(
+					target: LOG_TARGET,
+					"Skipped the entry of {:?} into lottery {} round {}: {:?}",
+					who,
+					lottery_id,
+					round,
+					error,
+				);
+				return;
+			}
+
+			// 3. Takes the round out of the subscription, ending it after the last one
+			subscription.funds = subscription.funds.saturating_sub(price);
+			subscription.remaining_rounds = subscription.remaining_rounds.saturating_sub(1);
+			let remaining_rounds = subscription.remaining_rounds;
+			Self::deposit_event(Event::AutoEntered { lottery_id, round, who: who.clone(), remaining_rounds });
+			if remaining_rounds == 0 {
+				let _ = Self::end_subscription(lottery_id, who, subscription.funds);
+			} else {
+				Subscriptions::<T>::insert(lottery_id, who, subscription);
+			}
+		}
+
+		// Removes the subscription, refunding the funds left
+		fn end_subscription(lottery_id: LotteryId, who: &T::AccountId, funds: BalanceOf<T>) -> DispatchResult {
+			Subscriptions::<T>::remove(lottery_id, who);
+			let shortfall = Self::release_ticket_funds(lottery_id, who, funds)?;
+			let refunded = funds.saturating_sub(shortfall);
+			Self::deposit_event(Event::Unsubscribed { lottery_id, who: who.clone(), refunded });
+			Ok(())
+		}
+
+		// Refunds the round if its sales closed without enough participants, as it
+		// can never be drawn. Returns whether the round was refunded
+		fn refund_if_undersubscribed(lottery_id: LotteryId) -> bool {
+			let count = Self::participant_count(lottery_id);
+			if Self::get_paused() || 
+				count == 0 || 
+				count >= T::MinParticipants::get() ||
+				Self::get_round_end(lottery_id).is_none() ||
+				!Self::sales_closed(lottery_id) {
+				return false;
+			}
+			let round = Self::get_current_round(lottery_id);
+			let refunded = Self::refund_round(lottery_id);
+			Self::deposit_event(Event::RoundRefundedInsufficientParticipants { lottery_id, round, refunded });
+			true
+		}
+
+		// Draws the winners and pays out the prize within a storage transaction, so
+		// any failure, such as a failed transfer, rolls back every change made and 
+		// the round can be retried. The caller triggering the draw, if any, is 
+		// rewarded, which a failed draw rolls back too
+		fn do_award_prize(
+			lottery_id: LotteryId,
+			seed: DrawSeed,
+			caller: Option<&T::AccountId>,
+		) -> DispatchResult {
+			ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+			ensure!(Self::accepts_seed(lottery_id, seed), Error::<T>::SeedRevealRequired);
+			ensure!(!CancellationProgress::<T>::contains_key(lottery_id), Error::<T>::LotteryCancelling);
+			with_transaction(|| match Self::draw_and_pay(lottery_id, seed, caller) {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(error) => TransactionOutcome::Rollback(Err(error)),
+			})
+		}
+
+		fn draw_and_pay(
+			lottery_id: LotteryId,
+			seed: DrawSeed,
+			caller: Option<&T::AccountId>,
+		) -> DispatchResult {
+
+			// An empty participants list, which may be left behind by storage edits
+			// or migrations, is treated as no participants at all
+			match Some(Self::participants_of(lottery_id)).filter(|participants| !participants.is_empty()) {
+				Some(participants) => { 
+
+					// 1. Checks that there are enough participants, that the pot is worth
+					// winning, and that the randomness is old enough, before consuming any
+					ensure!(
+						participants.len() as u32 >= T::MinParticipants::get(),
+						Error::<T>::NotEnoughParticipants
+					);
+					ensure!(Self::current_pot(lottery_id) >= T::MinPot::get(), Error::<T>::PotTooSmall);
+					ensure!(Self::randomness_ready(seed), Error::<T>::RandomnessNotReady);
+
+					// The participants the round is drawn over, sorted by the SCALE encoding 
+					// of their accounts, so the hash can be reproduced off-chain from the 
+					// list regardless of the order they entered or are stored in. The hash
+					// is the blake2_256 hash of the encoded accounts, concatenated in order
+					let mut snapshot = participants.clone();
+					snapshot.sort_by_cached_key(|(who, _)| who.encode());
+					let encoded: Vec<u8> = snapshot.iter().flat_map(|(who, _)| who.encode()).collect();
+					let participants_hash = blake2_256(&encoded);
+					let participant_count = snapshot.len() as u32;
+					let tickets_sold = Self::total_tickets(&snapshot);
+
+					// The memos of the participants, in the order of the snapshot, mixed 
+					// into the randomness of the draw when enabled
+					let memo_hash = T::MixMemoEntropy::get().then(|| {
+						let memos: Vec<_> = snapshot
+							.iter()
+							.filter_map(|(who, _)| Self::get_ticket_memo(lottery_id, who))
+							.collect();
+						T::Hashing::hash_of(&memos)
+					});
+					let seed = memo_hash.map_or(seed, |memo_hash| Self::mix_entropy(seed, memo_hash.as_ref()));
+					
+					// 2. Collects the funds held for every participant into the lottery's 
+					// account, from where the prize is paid out, or to the fee destination 
+					// when the prize is paid in another asset. Funds slashed in the 
+					// meantime can't be collected, and are left out of the prize
+					let mut ticket_revenue = BalanceOf::<T>::zero();
+					for (who, _) in participants.iter() {
+						let held = ReservedFunds::<T>::take(lottery_id, who);
+						let collected = Self::collect_ticket_funds(lottery_id, who, held)?;
+						ticket_revenue = ticket_revenue.saturating_add(collected);
+					}
+
+					// The balance the round is settled with, which the fee, the burn, the 
+					// payouts and whatever stays in the account add up to
+					let pot = Self::pot_balance(lottery_id);
+
+					// 3. Selects the winners by drawing winning numbers among the tickets 
+					// issued in the round, and looking up their owners, so each participant
+					// is weighted by the number of tickets held. The numbers are drawn by a
+					// partial Fisher-Yates shuffle of the tickets seeded by a single random
+					// number, so no ticket is drawn twice. Numbers of refunded tickets 
+					// or of accounts that already won are skipped, up to a bounded number of
+					// draws, so each draw reads a single ticket owner whatever the number of
+					// participants. The lottery's own account is never a valid winner, and is
+					// skipped too. The winners still missing once the draws run out, as the
+					// numbers keep falling on the tickets of a few large holders, are drawn
+					// among the tickets of the other participants, so the round has as many
+					// winners as asked for, up to the participants. When there are no more
+					// winners than participants, every participant wins without drawing
+					// any number.
+					//
+					// The participants that reached the pity threshold win first, picked
+					// evenly among them when there are more than winners, whatever their 
+					// tickets, and the other winners are drawn as usual. This trades some
+					// of the fairness of the draw for retention: the odds of the accounts 
+					// that keep losing grow to certainty, at the expense of the odds of 
+					// the others, so the tickets are no longer the only thing weighing 
+					// on the chances of each participant
+					let winners_count = T::WinnersPerRound::get()
+						.max(1)
+						.min(participants.len() as u32);
+					let issued = Self::get_tickets_issued(lottery_id);
+					ensure!(issued > 0, Error::<T>::NotEnoughParticipants);
+					let round = Self::get_current_round(lottery_id);
+					let lottery_account = Self::get_pallet_account(lottery_id);
+					let mut winners = Vec::with_capacity(winners_count as usize);
+					let mut winning_numbers = Vec::with_capacity(winners_count as usize);
+					let mut drawn_at = BlockNumberFor::<T>::zero();
+					if winners_count == participants.len() as u32 {
+						winners.extend(
+							participants
+								.iter()
+								.map(|(who, _)| who.clone())
+								.filter(|who| *who != lottery_account),
+						);
+						drawn_at = frame_system::Pallet::<T>::block_number();
+					} else {
+						let draws = winners_count.saturating_mul(MAX_SAMPLING_ATTEMPTS);
+						let (random, randomness_block) = Self::random_bytes(participants.len() as u32, seed);
+						let threshold = T::PityThreshold::get();
+						if threshold > 0 {
+							let eligible: Vec<_> = snapshot
+								.iter()
+								.map(|(who, _)| (who, Self::get_loss_streak(lottery_id, who)))
+								.filter(|(who, streak)| *streak >= threshold && **who != lottery_account)
+								.collect();
+							let pity_seed = blake2_256(&(b"lottery/pity", &random).encode());
+							for index in Self::select_winner_indices(&pity_seed, eligible.len() as u32, winners_count) {
+								let (who, streak) = eligible[index as usize];
+								winners.push(who.clone());
+								drawn_at = randomness_block;
+								Self::deposit_event(Event::PityAwarded { lottery_id, round, who: who.clone(), streak });
+							}
+						}
+						for winning_number in Self::select_winner_indices(&random, issued, draws) {
+							if winners.len() as u32 >= winners_count {
+								break;
+							}
+							let Some(winner) = Self::get_ticket_owner(lottery_id, winning_number) else {
+								continue;
+							};
+							if winner == lottery_account {
+								log::warn!(target: LOG_TARGET, "Lottery {} drew its own account", lottery_id);
+								continue;
+							}
+							if winners.contains(&winner) {
+								continue;
+							}
+							winners.push(winner);
+							winning_numbers.push(winning_number);
+							drawn_at = randomness_block;
+							Self::deposit_event(Event::DrawComputed { 
+								lottery_id, 
+								round, 
+								winning_number, 
+								randomness_block,
+							});
+						}
+
+						// Each missing winner takes a read of the tickets of every participant
+						// left, and a number drawn among them
+						let mut position = 0u32;
+						while (winners.len() as u32) < winners_count {
+							let remaining: Vec<_> = snapshot
+								.iter()
+								.filter(|(who, _)| *who != lottery_account && !winners.contains(who))
+								.map(|(who, _)| (who, Self::get_ticket_numbers(lottery_id, who)))
+								.collect();
+							let tickets = remaining
+								.iter()
+								.fold(0u32, |total, (_, numbers)| total.saturating_add(numbers.len() as u32));
+							if tickets == 0 {
+								break;
+							}
+							let subseed = blake2_256(&(b"lottery/remaining", &random, position).encode());
+							position.saturating_inc();
+							let mut index = Self::select_winner_index(&subseed, tickets);
+							for (who, numbers) in remaining {
+								let Some(&winning_number) = numbers.get(index as usize) else {
+									index = index.saturating_sub(numbers.len() as u32);
+									continue;
+								};
+								winners.push(who.clone());
+								winning_numbers.push(winning_number);
+								drawn_at = randomness_block;
+								Self::deposit_event(Event::DrawComputed {
+									lottery_id,
+									round,
+									winning_number,
+									randomness_block,
+								});
+								break;
+							}
+						}
+					}
+
+					// Only the lottery's own account, or participants left without tickets,
+					// can leave the round with fewer winners than asked for
+					ensure!(!winners.is_empty(), Error::<T>::NotEnoughParticipants);
+					let winners_count = winners.len() as u32;
+
+					// The winners start a new streak, and the other participants extend theirs
+					if T::PityThreshold::get() > 0 {
+						for (who, _) in snapshot.iter() {
+							if winners.contains(who) {
+								LossStreak::<T>::remove(lottery_id, who);
+							} else {
+								LossStreak::<T>::mutate(lottery_id, who, |streak| streak.saturating_inc());
+							}
+						}
+					}
+
+					// 4. Splits the total prize, which includes the jackpot carried over 
+					// from previous rounds, between the caller's reward, the fee, the burn,
+					// the seed of the next round and the winners. The fee, the burn and the seed are 
+					// rounded down so any dust goes to the winners, and each of them is 
+					// limited to what the previous ones leave, so they always add up 
+					// to the prize. No fee is charged when every winner is exempt from 
+					// it, while the burn still applies, as it's no charge of the treasury.
+					//
+					// A fixed prize is paid whole to the winners instead, with no reward, 
+					// fee, burn nor seed taken from it, while the ticket funds of the round
+					// go to the fee destination, and the rest of the funds is carried over
+					JackpotCarryover::<T>::remove(lottery_id);
+					let mut prize = Self::available_pot(lottery_id);
+					let prize_source = Self::get_prize_source(lottery_id);
+					let fixed_prize = match prize_source {
+						PrizeSource::Pool => false,
+						PrizeSource::Fixed { amount } => {
+
+							// The funds held apart from the ticket funds must cover the prize, 
+							// failing the draw otherwise, which rolls back the collection
+							if Self::ticket_funds_in_pot() {
+								prize = prize.saturating_sub(ticket_revenue);
+							}
+							ensure!(prize >= amount, Error::<T>::PotTooSmall);
+							if Self::ticket_funds_in_pot() && !ticket_revenue.is_zero() {
+								Self::pay_out(lottery_id, &T::FeeDestination::get(), ticket_revenue)?;
+							}
+							JackpotCarryover::<T>::insert(lottery_id, prize.saturating_sub(amount));
+							prize = amount;
+							true
+						},
+					};
+					if let Some(max_prize) = T::MaxPrize::get().filter(|max_prize| prize > *max_prize) {
+
+						// The excess stays in the lottery's account, to be included 
+						// in the prize of the next round
+						let carried = prize.saturating_sub(max_prize);
+						JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(carried));
+						prize = max_prize;
+						Self::deposit_event(Event::PrizeCapped { lottery_id, round, paid: prize, carried });
+					}
+
+					// The caller is rewarded before the prize is split, unless the reward 
+					// is too small to be received, in which case it goes to the winners. 
+					// A draw scheduled as the lottery's own account earns no reward, as 
+					// it would be paid to the pot itself
+					let mut draw_reward = BalanceOf::<T>::zero();
+					if let Some(caller) = caller.filter(|caller| !fixed_prize && **caller != lottery_account) {
+						let reward = T::DrawReward::get().min(prize);
+						if !reward.is_zero() && Self::can_receive(caller, reward) {
+							Self::pay_out(lottery_id, caller, reward)?;
+							prize = prize.saturating_sub(reward);
+							draw_reward = reward;
+						}
+					}
+					let fee_exempt = winners.iter().all(|winner| FeeExempt::<T>::contains_key(winner));
+					let mut fee_amount = if fee_exempt || fixed_prize {
+						Zero::zero()
+					} else {
+						T::FeePercent::get().mul_floor(prize)
+					};
+					let (mut burned, retained) = if fixed_prize {
+						(Zero::zero(), Zero::zero())
+					} else {
+						let burned = T::BurnPercent::get().mul_floor(prize).min(prize.saturating_sub(fee_amount));
+						let retained = T::SeedNextRoundPercent::get()
+							.mul_floor(prize)
+							.min(prize.saturating_sub(fee_amount).saturating_sub(burned));
+						(burned, retained)
+					};
+					let winners_amount = prize
+						.saturating_sub(fee_amount)
+						.saturating_sub(burned)
+						.saturating_sub(retained);
+
+					// The seed stays in the lottery's account, to be included in the 
+					// prize of the next round along with any excess over the cap
+					JackpotCarryover::<T>::mutate(lottery_id, |carried| *carried = carried.saturating_add(retained));
+
+					// 5. Splits the winners' share evenly, and sends the division remainder
+					// to the dust destination. The part left to the winners goes to the 
+					// first winner drawn
+					let share = winners_amount
+						.checked_div(&winners_count.into())
+						.ok_or(Error::<T>::ArithmeticOverflow)?;
+					let dust = winners_amount.saturating_sub(share.saturating_mul(winners_count.into()));
+					let dust = match T::DustDestination::get() {
+						DustDestination::Winner => dust,
+						DustDestination::Treasury => {
+							fee_amount = fee_amount.saturating_add(dust);
+							Zero::zero()
+						},
+						DustDestination::Carryover => {
+							JackpotCarryover::<T>::mutate(lottery_id, |carried| *carried = carried.saturating_add(dust));
+							Zero::zero()
+						},
+						DustDestination::Burn => {
+							burned = burned.saturating_add(dust);
+							Zero::zero()
+						},
+					};
+
+					// 6. Transfers the fee to its destination, burns its share of the prize,
+					// and records the shares owed to the winners, to be paid out on idle 
+					// blocks or claimed when convenient. Prizes not claimed yet are added 
+					// up, and their deadline restarts, while the expired ones go back into
+					// the pot first, so they can't be revived by winning again
+					let now = frame_system::Pallet::<T>::block_number();
+					if !fee_amount.is_zero() {
+						Self::pay_out(lottery_id, &T::FeeDestination::get(), fee_amount)?;
+					}
+					if !burned.is_zero() {
+						Self::burn(lottery_id, burned)?;
+					}
+					let mut payouts = Vec::with_capacity(winners.len());
+					for (position, winner) in winners.into_iter().enumerate() {
+						let amount = if position == 0 { share.saturating_add(dust) } else { share };
+						Self::owe_prize(lottery_id, &winner, amount, now);
+						LifetimeWins::<T>::mutate(&winner, |wins| wins.saturating_inc());
+						LifetimeWinnings::<T>::mutate(&winner, |won| *won = won.saturating_add(amount));
+						payouts.push((winner, amount));
+					}
+
+					// 7. Records the winners in the history and queues their payouts, 
+					// and resets the participants list to get ready for another lottery round
+					let pending: Vec<_> = payouts.iter().map(|(winner, _)| winner.clone()).collect();
+					PayoutTotals::<T>::insert(lottery_id, round, pending.len() as u32);
+					PendingPayouts::<T>::insert(lottery_id, round, BoundedVec::truncate_from(pending));
+					Self::record_winners(lottery_id, payouts.iter().map(|(winner, amount)| WinnerRecord {
+						round,
+						winner: winner.clone(),
+						prize: *amount,
+						fee: fee_amount,
+						burned,
+						pot,
+						participants_hash,
+						participant_count,
+					}));
+					#[cfg(feature = "full-snapshots")]
+					Self::archive_participants(lottery_id, round, BoundedVec::truncate_from(snapshot));
+					Self::clear_participants(lottery_id);
+					Self::clear_tickets(lottery_id);
+					Self::clear_round_end(lottery_id);
+					RoundPrizeSource::<T>::remove(lottery_id);
+					SeedCommitments::<T>::remove(lottery_id);
+					RoundDrawn::<T>::insert(lottery_id, true);
+					CurrentRound::<T>::insert(lottery_id, round.saturating_add(1));
+					Self::record_draw_time(lottery_id);
+
+					// 8. Flags the items of the winners' purchases, and forgets the items of 
+					// the round. The items are kept by their owners as collectibles
+					#[cfg(feature = "nfts")]
+					Self::flag_winning_items(lottery_id, round, &payouts);
+
+					// 9. Notifies the other modules of the winners, along with their wins
+					for (winner, amount) in payouts.iter() {
+						T::OnWinner::on_winner(winner, *amount, Self::get_lifetime_wins(winner));
+					}
+
+					// 10. Notify the events, summing the whole round up on a last one 
+					// when enabled
+					let summary = T::EmitRoundSummary::get().then(|| payouts.clone());
+					Self::deposit_event(Event::PrizesAwarded { 
+						lottery_id,
+						round,
+						winners: payouts,
+						winning_numbers,
+						fee_amount,
+						burned,
+						retained,
+						pot,
+						ticket_revenue,
+						memo_hash,
+						draw_reward,
+						prize_source,
+					});
+					if let Some(winners) = summary {
+						Self::deposit_event(Event::RoundCompleted {
+							lottery_id,
+							round,
+							participant_count,
+							tickets_sold,
+							pot,
+							winners,
+							fee: fee_amount,
+							burned,
+							carryover_out: Self::get_jackpot_carryover(lottery_id),
+							randomness_block: drawn_at,
+						});
+					}
+
+					// 11. Returns the creation deposit, now that the lottery is drawn
+					if let Some((creator, deposit)) = CreationDeposits::<T>::take(lottery_id) {
+						let amount = deposit.saturating_sub(T::Currency::unreserve_named(&Self::reserve_id(), &creator, deposit));
+						Self::deposit_event(Event::CreationDepositReturned { lottery_id, creator, amount });
+					}
+				}, 
+				None => {
+					// Leaves the funds in the pallet account and moves to the next 
+					// round, so the next winner gets the accumulated pot
+					let amount = Self::available_pot(lottery_id);
+					let new_round = Self::get_current_round(lottery_id).saturating_add(1);
+					Self::clear_participants(lottery_id);
+					Self::clear_tickets(lottery_id);
+					RoundPrizeSource::<T>::remove(lottery_id);
+					JackpotCarryover::<T>::insert(lottery_id, amount);
+					CurrentRound::<T>::insert(lottery_id, new_round);
+					Self::record_draw_time(lottery_id);
+
+					// Notify the events (No participants)
+					Self::deposit_event(Event::ThereAreNoParticipants { lottery_id });
+					Self::deposit_event(Event::JackpotRolledOver { lottery_id, amount, new_round });
+				}
+			};
+
+			Ok(())
+		}
+
+		// Checks that the signer's last purchase is older than the buy cooldown, and 
+		// records the new one. The cooldown applies to the signer across every 
+		// lottery and is not reset on a new round, so a round starting gives no 
+		// way around it
+		fn throttle_buy(signer: &T::AccountId) -> DispatchResult {
+			let cooldown = T::BuyCooldown::get();
+			if cooldown.is_zero() {
+				return Ok(());
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(last) = Self::get_last_buy(signer) {
+				ensure!(now.saturating_sub(last) >= cooldown, Error::<T>::BuyTooSoon);
+			}
+			LastBuy::<T>::insert(signer, now);
+			Ok(())
+		}
+
+		// Buys tickets for the beneficiary, charging their cost to the payer. Returns
+		// whether the tickets were bought, which they aren't when the beneficiary 
+		// already participates and the duplicate purchase policy ignores the purchase
+		fn do_buy_ticket(
+			payer: &T::AccountId,
+			beneficiary: T::AccountId,
+			lottery_id: LotteryId,
+			quantity: u32,
+		) -> Result<bool, DispatchError> {
+
+			// 1. Looks up the lottery, and checks that it is not paused
+			let lottery = Self::get_lottery(lottery_id).ok_or(Error::<T>::LotteryNotFound)?;
+			ensure!(!Self::get_paused(), Error::<T>::LotteryPaused);
+
+			// 2. Checks that the beneficiary is allowed to participate, and is not 
+			// the lottery's own account
+			ensure!(
+				beneficiary != Self::get_pallet_account(lottery_id),
+				Error::<T>::InvalidParticipant
+			);
+			Self::ensure_allowed(&beneficiary)?;
+			T::EligibilityCheck::ensure_eligible(&beneficiary)?;
+
+			// 3. Checks that at least one ticket is being bought, while the sales are open
+			ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+			Self::ensure_sales_open(lottery_id)?;
+
+			// 4. Applies the duplicate purchase policy when the beneficiary already 
+			// participates, before anything is charged
+			let held = Self::tickets_held(lottery_id, &beneficiary);
+			if held.is_some() {
+				match T::DuplicatePurchase::get() {
+					DuplicatePurchase::Reject => return Err(Error::<T>::AccountAlreadyParticipating.into()),
+					DuplicatePurchase::Idempotent => return Ok(false),
+					DuplicatePurchase::AllowMultiple => {},
+				}
+			}
+
+			// 5. Prices the tickets given the ones already sold in the round, and 
+			// checks that the payer has enough balance to afford all of them
+			let cost = Self::tickets_cost(&lottery, Self::round_tickets(lottery_id), quantity)?;
+			ensure!(Self::can_afford(payer, cost), Error::<T>::NotEnoughCurrency);
+			Self::ensure_escrow_room(lottery_id, cost)?;
+
+			// 6. Adds the tickets to the beneficiary's entry, registering the beneficiary
+			// as a new participant for the prize if it is the first purchase.
+			// The tickets held add up across purchases, up to the per account limit
+			let count = Self::participant_count(lottery_id);
+			let round_started = count == 0;
+			let mut lottery_full = false;
+			let tickets = match held {
+				Some(held) => held.saturating_add(quantity),
+				None => {
+					ensure!(count < lottery.max_participants, Error::<T>::LotteryFull);
+					lottery_full = count.saturating_add(1) >= lottery.max_participants;
+					quantity
+				}
+			};
+			ensure!(tickets <= T::MaxTicketsPerAccount::get(), Error::<T>::TicketLimitReached);
+
+			// 7. Holds the tickets cost paid until it is collected for the prize, 
+			// which is refunded to the beneficiary if the tickets are given up. The
+			// funds are moved before the entry is stored, so a transfer failing 
+			// despite the balance check, such as one that would reap the payer, 
+			// leaves no participant registered without having paid
+			Self::hold_ticket_funds(lottery_id, payer, &beneficiary, cost)?;
+			ReservedFunds::<T>::try_mutate(lottery_id, &beneficiary, |funds| {
+				*funds = funds.checked_add(&cost).ok_or(Error::<T>::ArithmeticOverflow)?;
+				Ok::<_, Error<T>>(())
+			})?;
+
+			// 8. Stores the entry, starting the sales of the round on its first 
+			// purchase, and numbers the tickets bought
+			if round_started {
+				Self::start_sales(lottery_id);
+			}
+			Self::set_tickets(lottery_id, &beneficiary, tickets)?;
+			Self::issue_tickets(lottery_id, &beneficiary, quantity)?;
+			TotalTicketsSold::<T>::mutate(|sold| *sold = sold.saturating_add(quantity.into()));
+
+			// 9. Mints the item of the purchase to the beneficiary. A failed mint fails
+			// the call, rolling back the whole purchase
+			#[cfg(feature = "nfts")]
+			Self::mint_ticket_item(lottery_id, &beneficiary)?;
+			
+			// 10. Notify the events
+			let round = Self::get_current_round(lottery_id);
+			if round_started {
+				Self::deposit_event(Event::RoundStarted { lottery_id, round });
+			}
+			Self::deposit_event(Event::TicketBought { 
+				lottery_id, 
+				round, 
+				who: beneficiary, 
+				payer: payer.clone(), 
+				quantity,
+				cost,
+			});
+			if lottery_full {
+				Self::deposit_event(Event::LotteryFull { lottery_id, round });
+			}
+			Ok(true)
+		}
+
+		// The purchase is charged as usual, unless no tickets were bought
+		fn purchase_post_info(bought: bool) -> PostDispatchInfo {
+			if bought {
+				().into()
+			} else {
+				Pays::No.into()
+			}
+		}
+
+		fn do_claim_prize(lottery_id: LotteryId, winner: T::AccountId) -> DispatchResult {
+
+			// 1. Takes the prize owed to the winner. A prize below the existential 
+			// deposit can't create the winner's account, so it's kept owed to the 
+			// winner instead, adding up with the next prizes won until it can be paid
+			let (owed, _) = Self::get_unclaimed_prize(lottery_id, &winner)
+				.ok_or(Error::<T>::NoPrizeToClaim)?;
+			ensure!(Self::can_receive(&winner, owed), Error::<T>::PrizeBelowExistentialDeposit);
+			let amount = Self::take_prize(lottery_id, &winner)?;
+
+			// 2. Transfers the prize from the lottery's account to the winner
+			Self::pay_out(lottery_id, &winner, amount)?;
+
+			// 3. Notify the event
+			Self::deposit_event(Event::PrizeClaimed { lottery_id, winner, amount });
+			Ok(())
+		}
+
+		// Takes the prize owed to the winner out of the unclaimed prizes, unless the 
+		// claim deadline has passed and the prize belongs to the pot again
+		fn take_prize(lottery_id: LotteryId, winner: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+			let (amount, drawn_at) = Self::get_unclaimed_prize(lottery_id, winner)
+				.ok_or(Error::<T>::NoPrizeToClaim)?;
+			ensure!(!Self::prize_expired(drawn_at), Error::<T>::PrizeExpired);
+			UnclaimedPrizes::<T>::remove(lottery_id, winner);
+			TotalUnclaimed::<T>::mutate(lottery_id, |total| *total = total.saturating_sub(amount));
+			Ok(amount)
+		}
+
+		// Records the prize owed to the winner, kept in the lottery's account until
+		// claimed. A prize not claimed yet is added up and its deadline restarts,
+		// while an expired one goes back into the pot first, so it can't be revived
+		// by winning again
+		fn owe_prize(lottery_id: LotteryId, winner: &T::AccountId, amount: BalanceOf<T>, now: BlockNumberFor<T>) {
+			if let Some((expired, drawn_at)) = Self::get_unclaimed_prize(lottery_id, winner) {
+				if Self::prize_expired(drawn_at) {
+					Self::expire_prize(lottery_id, winner.clone(), expired);
+				}
+			}
+			UnclaimedPrizes::<T>::mutate(lottery_id, winner, |owed| {
+				let (owed_amount, _) = owed.unwrap_or_default();
+				*owed = Some((owed_amount.saturating_add(amount), now));
+			});
+			TotalUnclaimed::<T>::mutate(lottery_id, |total| *total = total.saturating_add(amount));
+			TotalPrizesPaid::<T>::mutate(|paid| *paid = paid.saturating_add(amount));
+			Self::deposit_event(Event::PrizeClaimable { lottery_id, winner: winner.clone(), amount });
+		}
+
+		// Moves an expired prize back into the pot, as a jackpot carried over to
+		// the next round
+		fn expire_prize(lottery_id: LotteryId, who: T::AccountId, amount: BalanceOf<T>) {
+			UnclaimedPrizes::<T>::remove(lottery_id, &who);
+			TotalUnclaimed::<T>::mutate(lottery_id, |total| *total = total.saturating_sub(amount));
+			JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_add(amount));
+			Self::deposit_event(Event::PrizeExpired { lottery_id, who, amount });
+		}
+
+		// Pays out the prize owed to the winner on the payout location. The prize is 
+		// moved to the reserve account of the payout location, and a message is sent
+		// there to deposit the same amount of the asset to the winner's account
+		#[cfg(feature = "xcm-payout")]
+		fn do_send_prize(lottery_id: LotteryId, winner: &T::AccountId) -> DispatchResult {
+
+			// 1. Takes the prize owed to the winner
+			let amount = Self::take_prize(lottery_id, winner)?;
+
+			// 2. Moves the prize to the reserve backing the asset on the payout location
+			Self::pay_out(lottery_id, &T::PayoutReserve::get(), amount)?;
+
+			// 3. Sends the message depositing the prize to the winner's account
+			let asset = MultiAsset {
+				id: xcm::v3::AssetId::Concrete(T::PayoutAsset::get()),
+				fun: Fungibility::Fungible(amount.saturated_into()),
+			};
+			let message = Xcm(vec![
+				Instruction::ReserveAssetDeposited(asset.clone().into()),
+				Instruction::ClearOrigin,
+				Instruction::BuyExecution { fees: asset, weight_limit: WeightLimit::Unlimited },
+				Instruction::DepositAsset {
+					assets: MultiAssetFilter::Wild(WildMultiAsset::AllCounted(1)),
+					beneficiary: T::AccountIdToLocation::convert(winner.clone()),
+				},
+			]);
+			send_xcm::<T::XcmRouter>(T::PayoutLocation::get(), message)
+				.map_err(|_| Error::<T>::PayoutNotSent)?;
+
+			// 4. Notify the event
+			Self::deposit_event(Event::PrizeClaimed { lottery_id, winner: winner.clone(), amount });
+			Ok(())
+		}
+
+		// Mints a new item of the ticket collection to the participant, and stores
+		// it along with the items of its previous purchases in the round
+		#[cfg(feature = "nfts")]
+		fn mint_ticket_item(lottery_id: LotteryId, who: &T::AccountId) -> DispatchResult {
+			let item: T::ItemId = NextTicketItem::<T>::mutate(|next| {
+				let item = *next;
+				*next = next.saturating_add(1);
+				item
+			}).into();
+			<T::Nfts as nonfungibles::Mutate<_>>::mint_into(&T::TicketCollection::get(), &item, who)?;
+			TicketItems::<T>::try_mutate(lottery_id, who, |items| items.try_push(item))
+				.map_err(|_| Error::<T>::TicketLimitReached)?;
+			Ok(())
+		}
+
+		// Sets the round won as an attribute of the items of each winner, ignoring 
+		// collections that don't support attributes, and clears the round's items
+		#[cfg(feature = "nfts")]
+		fn flag_winning_items(lottery_id: LotteryId, round: u32, winners: &[(T::AccountId, BalanceOf<T>)]) {
+			let collection = T::TicketCollection::get();
+			for (winner, _) in winners {
+				for item in Self::get_ticket_items(lottery_id, winner).iter() {
+					let _ = <T::Nfts as nonfungibles::Mutate<_>>::set_attribute(
+						&collection,
+						item,
+						b"winner",
+						&round.encode());
+				}
+			}
+			let _ = TicketItems::<T>::clear_prefix(lottery_id, T::MaxParticipants::get(), None);
+		}
+
+		// When the allowlist is required, only the accounts in it can participate, and
+		// when the identities are required, only the accounts with a verified one
+		fn ensure_allowed(who: &T::AccountId) -> DispatchResult {
+			if T::RequireAllowList::get() {
+				ensure!(AllowList::<T>::contains_key(who), Error::<T>::NotAllowed);
+			}
+			if T::RequireIdentity::get() {
+				ensure!(T::Identity::is_verified(who), Error::<T>::NoVerifiedIdentity);
+			}
+			Ok(())
+		}
+
+		// Whether the automatic draw of the lottery is due on the schedule. In time, 
+		// the timestamp compared is the previous block's, as the current block's 
+		// timestamp is only set after the block is initialized
+		fn draw_due(
+			lottery_id: LotteryId,
+			now: BlockNumberFor<T>,
+			schedule: DrawSchedule<BlockNumberFor<T>>,
+		) -> bool {
+			match schedule {
+				DrawSchedule::Blocks(interval) => 
+					now.saturating_sub(Self::get_last_draw(lottery_id)) >= interval,
+				DrawSchedule::Timestamp { interval_ms } => 
+					Self::now_ms().saturating_sub(Self::get_last_draw_timestamp(lottery_id)) >= interval_ms,
+			}
+		}
+
+		// Records the block of a draw, along with its timestamp when drawing on a 
+		// schedule in time
+		fn record_draw_time(lottery_id: LotteryId) {
+			LastDraw::<T>::insert(lottery_id, frame_system::Pallet::<T>::block_number());
+			if let DrawSchedule::Timestamp { .. } = T::DrawSchedule::get() {
+				LastDrawTimestamp::<T>::insert(lottery_id, Self::now_ms());
+			}
+		}
 
+		fn now_ms() -> u64 {
+			T::Timestamp::now().as_millis().saturated_into()
+		}
+
+		// Rolls the scratches due on the block, on its randomness, which wasn't
+		// known yet when they were bought. A winning scratch is owed the scratch
+		// prize out of the funds making up the next prize, up to what they hold
+		// by then, and it's claimed like the prizes of the rounds
+		fn settle_scratches(now: BlockNumberFor<T>) -> Weight {
+			let pending = PendingScratches::<T>::take(now);
+			let weight = T::WeightInfo::settle_scratches(pending.len() as u32);
+			let odds = T::ScratchOdds::get().deconstruct();
+			for (lottery_id, who) in pending {
+				let (roll, _) = Self::random_below(Perbill::ACCURACY, 0, DrawSeed::OnChain);
+				let won = roll < odds;
+				let amount = if won {
+					T::ScratchPrize::get().min(Self::available_pot(lottery_id))
+				} else {
+					Zero::zero()
+				};
+				if !amount.is_zero() {
+					JackpotCarryover::<T>::mutate(lottery_id, |carryover| *carryover = carryover.saturating_sub(amount));
+					Self::owe_prize(lottery_id, &who, amount, now);
+				}
+				Self::deposit_event(Event::ScratchResult { lottery_id, who, won, amount });
+			}
+			weight
+		}
+
+		// Emits a warning for each round undrawn for longer than the stale round
+		// blocks. It's emitted on the block after the threshold is crossed only, 
+		// which keeps it to a single read of each round's start
+		fn report_stale_rounds(now: BlockNumberFor<T>) -> Weight {
+			let threshold = T::StaleRoundBlocks::get();
+			if threshold.is_zero() {
+				return Weight::zero();
+			}
+			let mut reads = 0u64;
+			for (lottery_id, started) in RoundStart::<T>::iter() {
+				reads.saturating_inc();
+				let age = now.saturating_sub(started);
+				if age == threshold.saturating_add(1u32.into()) {
+					let round = Self::get_current_round(lottery_id);
+					reads.saturating_inc();
+					Self::deposit_event(Event::RoundStale { lottery_id, round, age });
+				}
+			}
+			T::DbWeight::get().reads(reads)
+		}
+
+		// Records the start of a round, and sets the end of its ticket sales
+		fn start_sales(lottery_id: LotteryId) {
+			RoundDrawn::<T>::remove(lottery_id);
+			RoundStart::<T>::insert(lottery_id, frame_system::Pallet::<T>::block_number());
+			let duration = T::SalesDuration::get();
+			if !duration.is_zero() {
+				let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+				RoundEnd::<T>::insert(lottery_id, end);
+				#[cfg(feature = "scheduler")]
+				Self::schedule_draw(lottery_id, end);
+			}
+		}
+
+		// Removes the start and the end of the ticket sales of a round that is over, 
+		// along with its scheduled draw, if any
+		fn clear_round_end(lottery_id: LotteryId) {
+			RoundStart::<T>::remove(lottery_id);
+			RoundEnd::<T>::remove(lottery_id);
+			#[cfg(feature = "scheduler")]
+			let _ = T::Scheduler::cancel_named(Self::draw_task_name(lottery_id));
+		}
+
+		// Schedules the draw of the round, as the lottery's account, at the end of
+		// its ticket sales. A draw already scheduled is moved to the new deadline.
+		// Failing to schedule it is not an error, as the round can still be drawn
+		// on an automatic draw or by any account
+		#[cfg(feature = "scheduler")]
+		fn schedule_draw(lottery_id: LotteryId, end: BlockNumberFor<T>) {
+			let name = Self::draw_task_name(lottery_id);
+			if T::Scheduler::reschedule_named(name, DispatchTime::At(end)).is_ok() {
+				return;
+			}
+			let origin: T::PalletsOrigin =
+				frame_system::RawOrigin::Signed(Self::get_pallet_account(lottery_id)).into();
+			let scheduled = T::Preimages::bound(CallOf::<T>::from(Call::draw { lottery_id }))
+				.and_then(|call| T::Scheduler::schedule_named(
+					name,
+					DispatchTime::At(end),
+					None,
+					LOWEST_PRIORITY,
+					origin,
+					call));
+			if let Err(error) = scheduled {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to schedule the draw of lottery {}: {:?}",
+					lottery_id,
+					error,
+				);
+			}
+		}
+
+		// Name of the scheduled draw task of the lottery
+		#[cfg(feature = "scheduler")]
+		fn draw_task_name(lottery_id: LotteryId) -> [u8; 32] {
+			(b"lottery/draw", lottery_id).using_encoded(blake2_256)
+		}
+
+		// Checks that the round of the lottery has a deadline that has already 
+		// passed, that it has not been drawn yet, and that it doesn't wait for a seed
+		fn ensure_can_draw(lottery_id: LotteryId) -> DispatchResult {
+			ensure!(Lotteries::<T>::contains_key(lottery_id), Error::<T>::LotteryNotFound);
+			ensure!(!Self::get_round_drawn(lottery_id), Error::<T>::RoundAlreadyDrawn);
+			ensure!(
+				Self::get_round_end(lottery_id).is_some() && Self::sales_closed(lottery_id),
+				Error::<T>::DrawNotYetAllowed
+			);
+			ensure!(
+				!SeedCommitments::<T>::contains_key(lottery_id) && 
+					Self::accepts_seed(lottery_id, DrawSeed::OnChain),
+				Error::<T>::SeedRevealRequired
+			);
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
+		// Whether the lottery can be drawn with the seed, which must be a revealed
+		// one for the lotteries drawn by commit and reveal
+		fn accepts_seed(lottery_id: LotteryId, seed: DrawSeed) -> bool {
+			match (Self::get_lottery_randomness(lottery_id), seed) {
+				(RandomnessSource::CommitReveal, DrawSeed::OnChain) => false,
+				_ => true,
+			}
+		}
+
+		// Whether the round has the participants and the pot required to be drawn,
+		// with randomness old enough to draw it
+		fn draw_thresholds_met(lottery_id: LotteryId) -> bool {
+			Self::participant_count(lottery_id) >= T::MinParticipants::get().max(1) &&
+				Self::current_pot(lottery_id) >= T::MinPot::get() &&
+				Self::randomness_ready(DrawSeed::OnChain)
+		}
+
+		// Whether the randomness module's randomness was generated at least the 
+		// randomness delay before the current block. The randomness of a fixed
+		// seed is not used, so it's always ready
+		fn randomness_ready(seed: DrawSeed) -> bool {
+			match seed {
+				#[cfg(feature = "test-helpers")]
+				DrawSeed::Fixed(_) => true,
+				_ => {
+					let (_, known_since) = T::MyRandomness::random_seed();
+					frame_system::Pallet::<T>::block_number().saturating_sub(known_since) >= 
+						T::RandomnessDelay::get()
+				},
+			}
+		}
+
+		// XORs the entropy into the seed of the draw, turning a draw on the randomness
+		// module alone into one mixed with the entropy. A fixed seed is kept as is, 
+		// so the draw can still be reproduced
+		fn mix_entropy(seed: DrawSeed, entropy: &[u8]) -> DrawSeed {
+			let mut mixed = match seed {
+				DrawSeed::OnChain => [0u8; 32],
+				DrawSeed::Revealed(revealed) => revealed,
+				#[cfg(feature = "test-helpers")]
+				DrawSeed::Fixed(_) => return seed,
+			};
+			mixed.iter_mut().zip(entropy.iter()).for_each(|(byte, entropy)| *byte ^= entropy);
+			DrawSeed::Revealed(mixed)
+		}
+
+		// Whether the lottery's account must keep funds after a payout, either
+		// carried over to the next round or owed to winners that didn't claim yet
+		fn holds_remaining_funds(lottery_id: LotteryId) -> bool {
+			!Self::get_jackpot_carryover(lottery_id).is_zero() || 
+				!Self::get_total_unclaimed(lottery_id).is_zero()
+		}
+
+		fn ensure_enabled() -> DispatchResult {
+			ensure!(Self::get_enabled(), Error::<T>::LotteryDisabled);
+			Ok(())
+		}
+
+		fn sales_closed(lottery_id: LotteryId) -> bool {
+			Self::get_round_end(lottery_id)
+				.map_or(true, |end| frame_system::Pallet::<T>::block_number() >= end)
+		}
+
+		fn ensure_sales_open(lottery_id: LotteryId) -> DispatchResult {
+			ensure!(!CancellationProgress::<T>::contains_key(lottery_id), Error::<T>::LotteryCancelling);
+			if let Some(end) = Self::get_round_end(lottery_id) {
+				ensure!(frame_system::Pallet::<T>::block_number() < end, Error::<T>::SalesClosed);
+			}
+			Ok(())
+		}
+
+		// Appends the winners to the history, evicting the oldest entries once the
+		// history is full
+		fn record_winners(
+			lottery_id: LotteryId,
+			records: impl IntoIterator<Item = WinnerRecord<T::AccountId, BalanceOf<T>>>,
+		) {
+			WinnerHistory::<T>::mutate(lottery_id, |history| {
+				for record in records {
+					if !history.is_empty() && history.len() as u32 >= T::MaxHistory::get() {
+						history.remove(0);
+					}
+					let _ = history.try_push(record);
+				}
+			});
+		}
+
+		// Archives the participants of the round, forgetting the oldest round 
+		// archived once the archive is full
+		#[cfg(feature = "full-snapshots")]
+		fn archive_participants(
+			lottery_id: LotteryId,
+			round: u32,
+			participants: BoundedVec<(T::AccountId, u32), T::MaxParticipants>,
+		) {
+			let archived = ArchivedRounds::<T>::mutate(lottery_id, |rounds| {
+				if rounds.is_full() && !rounds.is_empty() {
+					RoundParticipants::<T>::remove(lottery_id, rounds.remove(0));
+				}
+				rounds.try_push(round).is_ok()
+			});
+			if archived {
+				RoundParticipants::<T>::insert(lottery_id, round, participants);
+			}
+		}
+
+		fn prize_expired(drawn_at: BlockNumberFor<T>) -> bool {
+			let deadline = T::ClaimDeadline::get();
+			!deadline.is_zero() && 
+				frame_system::Pallet::<T>::block_number() >= drawn_at.saturating_add(deadline)
+		}
+
+		// The funds in the lottery's account that make up the next prize, which
+		// are all of them except the prizes not claimed yet and the funds prepaid
+		// by the subscribers
+		fn available_pot(lottery_id: LotteryId) -> BalanceOf<T> {
+			Self::pot_balance(lottery_id)
+				.saturating_sub(Self::get_total_unclaimed(lottery_id))
+				.saturating_sub(Self::prepaid_funds(lottery_id))
+		}
+
+		// The funds in the lottery's account that belong to someone: the jackpot 
+		// carried over, the prizes not claimed yet and the ticket funds of the 
+		// current round held there
+		fn owed_funds(lottery_id: LotteryId) -> BalanceOf<T> {
+			Self::get_jackpot_carryover(lottery_id)
+				.saturating_add(Self::get_total_unclaimed(lottery_id))
+				.saturating_add(Self::escrowed_ticket_funds(lottery_id))
+		}
+
+		// Checks that the lottery can take the amount without holding more funds 
+		// than the cap
+		fn ensure_escrow_room(lottery_id: LotteryId, amount: BalanceOf<T>) -> DispatchResult {
+			let Some(max_escrow) = T::MaxEscrow::get() else {
+				return Ok(());
+			};
+			let escrow = Self::pot_balance(lottery_id)
+				.saturating_add(Self::uncollected_ticket_funds(lottery_id));
+			ensure!(
+				escrow.checked_add(&amount).map_or(false, |escrow| escrow <= max_escrow),
+				Error::<T>::EscrowCapExceeded
+			);
+			Ok(())
+		}
+
+		/// Returns the account holding the funds of the lottery. Each lottery holds its 
+		/// funds in its own sub-account of the configured `PalletId`, so the funds of 
+		/// different lotteries, or of modules configured with different `PalletId`s,
+		/// don't commingle, and integrators can derive the account from the `PalletId`
+		/// constant and the lottery id alone
+		pub fn get_pallet_account(lottery_id: LotteryId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(lottery_id)
+		}
+
+		// The identifier of the funds the module reserves, which is its `PalletId`
+		pub(crate) fn reserve_id() -> [u8; 8] {
+			T::PalletId::get().0
+		}
+
+		// Adds up the price of each of the tickets bought, after the tickets
+		// already sold in the round, with every price kept between the minimum
+		// and the maximum.
+		// A total cost that doesn't fit the balance type is an overflow error
+		fn tickets_cost(
+			lottery: &LotteryConfig<BalanceOf<T>>,
+			sold: u32,
+			quantity: u32,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			match T::Pricing::get() {
+				PricingStrategy::Fixed => lottery.ticket_cost
+					.checked_mul(&quantity.into())
+					.ok_or(Error::<T>::ArithmeticOverflow.into()),
+				PricingStrategy::Linear { base, step } => {
+					let max_cost = T::MaxTicketCost::get();
+					let min_cost = T::MinTicketCost::get();
+					(0..quantity).try_fold(Zero::zero(), |cost: BalanceOf<T>, ticket| {
+						let price = step
+							.saturating_mul(sold.saturating_add(ticket).into())
+							.saturating_add(base)
+							.min(max_cost)
+							.max(min_cost);
+						cost.checked_add(&price).ok_or(Error::<T>::ArithmeticOverflow.into())
+					})
+				},
+			}
+		}
+
+		fn total_tickets(participants: &[(T::AccountId, u32)]) -> u32 {
+			participants
+				.iter()
+				.fold(0u32, |total, (_, tickets)| total.saturating_add(*tickets))
+		}
+
+		// Issues the next ticket numbers of the round to the participant
+		pub(crate) fn issue_tickets(lottery_id: LotteryId, who: &T::AccountId, count: u32) -> DispatchResult {
+			let first = Self::get_tickets_issued(lottery_id);
+			let next = first.checked_add(count).ok_or(Error::<T>::TicketLimitReached)?;
+			TicketNumbers::<T>::try_mutate(lottery_id, who, |numbers| {
+				for number in first..next {
+					numbers.try_push(number).map_err(|_| Error::<T>::TicketLimitReached)?;
+					TicketOwners::<T>::insert(lottery_id, number, who);
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			TicketsIssued::<T>::insert(lottery_id, next);
+			Ok(())
+		}
+
+		// Voids the highest ticket number held by the participant, which is refunded
+		fn void_ticket(lottery_id: LotteryId, who: &T::AccountId) {
+			let voided = TicketNumbers::<T>::mutate_exists(lottery_id, who, |numbers| {
+				let held = numbers.as_mut()?;
+				let position = (0..held.len()).max_by_key(|position| held[*position])?;
+				let number = held.swap_remove(position);
+				if held.is_empty() {
+					*numbers = None;
+				}
+				Some(number)
+			});
+			if let Some(number) = voided {
+				Self::void_number(lottery_id, number);
+			}
+		}
+
+		// Voids every ticket number held by the participant, from the highest one
+		fn void_tickets(lottery_id: LotteryId, who: &T::AccountId) {
+			let mut numbers = TicketNumbers::<T>::take(lottery_id, who).into_inner();
+			numbers.sort_unstable_by(|a, b| b.cmp(a));
+			for number in numbers {
+				Self::void_number(lottery_id, number);
+			}
+		}
+
+		// Voids a ticket number taken from its owner, moving the last number issued 
+		// in the round into its place, so the numbers issued stay contiguous and 
+		// every number drawn has an owner. The owner of the number voided must not 
+		// hold the last number unless it's the one voided, which voiding the numbers
+		// of each owner from the highest one ensures
+		fn void_number(lottery_id: LotteryId, number: u32) {
+			let last = Self::get_tickets_issued(lottery_id).saturating_sub(1);
+			TicketsIssued::<T>::insert(lottery_id, last);
+			if number == last {
+				TicketOwners::<T>::remove(lottery_id, number);
+				return;
+			}
+			match TicketOwners::<T>::take(lottery_id, last) {
+				Some(owner) => {
+					TicketNumbers::<T>::mutate(lottery_id, &owner, |numbers| {
+						if let Some(moved) = numbers.iter_mut().find(|moved| **moved == last) {
+							*moved = number;
+						}
+					});
+					TicketOwners::<T>::insert(lottery_id, number, owner);
+				},
+				None => TicketOwners::<T>::remove(lottery_id, number),
+			}
+		}
+
+		// Forgets the ticket numbers issued in the round
+		fn clear_tickets(lottery_id: LotteryId) {
+			let issued = TicketsIssued::<T>::take(lottery_id);
+			let _ = TicketOwners::<T>::clear_prefix(lottery_id, issued, None);
+			let _ = TicketNumbers::<T>::clear_prefix(lottery_id, T::MaxParticipants::get(), None);
+			let _ = TicketMemos::<T>::clear_prefix(lottery_id, T::MaxParticipants::get(), None);
+		}
+
+		// Gets an unbiased random number lower than the given bound, along with the
+		// block the randomness was generated at
+		fn random_below(
+			bound: u32,
+			participants: u32,
+			seed: DrawSeed,
+		) -> (u32, BlockNumberFor<T>) {
+			let (random, block) = Self::random_bytes(participants, seed);
+			(Self::select_winner_index(&random, bound), block)
+		}
+
+		// Gets random bytes from the randomness module, using a new nonce on each
+		// call so consecutive draws are different. The nonce is combined with the 
+		// parent block hash and the number of participants, so the subject can't be
+		// precomputed. A revealed seed is XORed with the randomness, so neither the
+		// oracle nor the block author can choose the result on their own
+		fn random_bytes(participants: u32, seed: DrawSeed) -> (Vec<u8>, BlockNumberFor<T>) {
+			let nonce = Self::get_and_increment_nonce();
+			let subject = (&nonce, frame_system::Pallet::<T>::parent_hash(), participants).encode();
+			let (mut random_bytes, block) = match seed {
+				#[cfg(feature = "test-helpers")]
+				DrawSeed::Fixed(seed) => (
+					blake2_256(&(seed, &nonce).encode()).to_vec(),
+					frame_system::Pallet::<T>::block_number(),
+				),
+				_ => {
+					#[cfg(feature = "vrf-randomness")]
+					let vrf = Self::vrf_randomness(&subject);
+					#[cfg(not(feature = "vrf-randomness"))]
+					let vrf = None;
+					vrf.unwrap_or_else(|| {
+						let (random_seed, block) = T::MyRandomness::random(&subject);
+						(random_seed.as_ref().to_vec(), block)
+					})
+				},
+			};
+
+			// Randomness too short to fill a u64, as returned by a misconfigured
+			// source, is hashed to a usable width instead of failing the draw
+			if random_bytes.len() < mem::size_of::<u64>() {
+				log::warn!(
+					target: LOG_TARGET,
+					"Randomness of {} bytes is too short, hashing it",
+					random_bytes.len(),
+				);
+				random_bytes = blake2_256(&random_bytes).to_vec();
+			}
+			if let DrawSeed::Revealed(seed) = seed {
+				random_bytes.iter_mut().zip(seed.iter()).for_each(|(byte, seed)| *byte ^= seed);
+			}
+			(random_bytes, block)
+		}
 
-		fn get_pallet_account() -> T::AccountId {
-			T::PalletId::get().into_account_truncating()
+		// Gets the randomness of the block authors' VRF outputs, if available. The
+		// VRF output of a block is only known once the block is authored, and its 
+		// author can still withhold the block to discard an unfavorable draw, so 
+		// a draw must never rely on the VRF output of the block it's included in:
+		// the source is expected to return the randomness of a past block, as the
+		// parent block randomness of BABE does. It's unavailable on the first 
+		// blocks of the chain and between epochs, where the draw falls back to
+		// the randomness module
+		#[cfg(feature = "vrf-randomness")]
+		fn vrf_randomness(subject: &[u8]) -> Option<(Vec<u8>, BlockNumberFor<T>)> {
+			let (random_seed, block) = T::ValidatorRandomness::random(subject);
+			random_seed.map(|random_seed| (random_seed.as_ref().to_vec(), block))
 		}
 
 		fn get_and_increment_nonce() -> Vec<u8> {
@@ -203,5 +4442,567 @@ pub mod pallet {
 			Nonce::<T>::put(nonce.wrapping_add(1));
 			nonce.encode()
 		}
+
+		// Checks the invariants of every lottery, failing with a message 
+		// describing the first one broken
+		#[cfg(feature = "try-runtime")]
+		pub(crate) fn do_try_state() -> Result<(), TryRuntimeError> {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				Lotteries::<T>::iter_keys().all(|id| id < Self::get_next_lottery_id()),
+				"Lottery stored with an id that was never assigned"
+			);
+
+			for (lottery_id, _) in Lotteries::<T>::iter() {
+
+				// 1. The participants list holds unique accounts, each one with 
+				// at least one ticket, and it is only stored while not empty
+				let participants = Self::participants_of(lottery_id);
+				#[cfg(not(feature = "indexed-participants"))]
+				ensure!(
+					Participants::<T>::get(lottery_id).map_or(true, |list| !list.is_empty()),
+					"Empty participants list stored instead of removed"
+				);
+				#[cfg(feature = "indexed-participants")]
+				ensure!(
+					participants.len() as u32 == Self::get_participant_count(lottery_id) &&
+						Self::total_tickets(&participants) == Self::get_tickets_held(lottery_id),
+					"Participants index doesn't match the participant and ticket counts"
+				);
+				let unique: BTreeSet<_> = participants.iter().map(|(who, _)| who).collect();
+				ensure!(unique.len() == participants.len(), "Duplicate participant in the list");
+				ensure!(
+					participants.iter().all(|(_, tickets)| *tickets > 0),
+					"Participant holding no tickets"
+				);
+				ensure!(
+					participants.iter().all(|(who, tickets)| {
+						Self::get_ticket_numbers(lottery_id, who).len() as u32 == *tickets
+					}),
+					"Participant's ticket numbers don't match its tickets"
+				);
+				ensure!(
+					(0..Self::get_tickets_issued(lottery_id))
+						.all(|number| TicketOwners::<T>::contains_key(lottery_id, number)),
+					"Ticket number issued without an owner"
+				);
+
+				// 2. The ticket funds are held only for the participants, and are
+				// still available to be collected for the prize. Participants added
+				// by the admin hold no funds
+				ensure!(
+					ReservedFunds::<T>::iter_key_prefix(lottery_id).all(|who| unique.contains(&who)),
+					"Funds held for an account that is not participating"
+				);
+				#[cfg(not(feature = "fungibles"))]
+				for (who, _) in participants.iter() {
+					ensure!(
+						T::Currency::reserved_balance_named(&Self::reserve_id(), who) >= 
+							Self::get_reserved_funds(lottery_id, who),
+						"Participant's reserved balance is lower than the ticket funds"
+					);
+				}
+
+				// 3. The lottery's account holds the prizes not claimed yet and the 
+				// jackpot carried over, along with the ticket funds when those are 
+				// transferred to it
+				let unclaimed = UnclaimedPrizes::<T>::iter_prefix_values(lottery_id)
+					.fold(BalanceOf::<T>::zero(), |total, (amount, _)| total.saturating_add(amount));
+				ensure!(
+					unclaimed == Self::get_total_unclaimed(lottery_id),
+					"Total of unclaimed prizes doesn't match the prizes owed"
+				);
+				let owed = unclaimed.saturating_add(Self::get_jackpot_carryover(lottery_id));
+				#[cfg(feature = "fungibles")]
+				let owed = if Self::ticket_funds_in_pot() {
+					ReservedFunds::<T>::iter_prefix_values(lottery_id)
+						.fold(owed, |owed, held| owed.saturating_add(held))
+				} else {
+					owed
+				};
+				ensure!(
+					Self::pot_balance(lottery_id) >= owed,
+					"Lottery account holds less than the funds owed"
+				);
+
+				// 4. The sales deadline is only set while the round has participants, 
+				// and a drawn round has moved the round counter forward, with the 
+				// last draw never ahead of the current block
+				ensure!(
+					Self::get_round_end(lottery_id).is_none() || !participants.is_empty(),
+					"Sales deadline set for a round without participants"
+				);
+				ensure!(
+					!Self::get_round_drawn(lottery_id) || Self::get_current_round(lottery_id) > 0,
+					"Round drawn without moving the round counter forward"
+				);
+				ensure!(Self::get_last_draw(lottery_id) <= now, "Last draw is ahead of the current block");
+			}
+
+			// The winnings of every account are part of the prizes awarded, which 
+			// also count the scratch prizes, and are only recorded for accounts 
+			// that won a draw
+			let winnings = LifetimeWinnings::<T>::iter()
+				.try_fold(BalanceOf::<T>::zero(), |total, (who, won)| {
+					ensure!(Self::get_lifetime_wins(&who) > 0, "Winnings recorded for an account that never won");
+					Ok::<_, TryRuntimeError>(total.saturating_add(won))
+				})?;
+			ensure!(
+				winnings <= Self::get_total_prizes_paid(),
+				"Lifetime winnings add up to more than the prizes awarded"
+			);
+			Ok(())
+		}
+	}
+
+	// By default, the participants of each lottery are held in a single list, which 
+	// keeps the order they entered in, but is read and written whole on every change
+	#[cfg(not(feature = "indexed-participants"))]
+	impl<T: Config> Pallet<T> {
+
+		pub(crate) fn participants_of(lottery_id: LotteryId) -> Vec<(T::AccountId, u32)> {
+			Self::get_participants(lottery_id).map_or_else(Vec::new, |participants| participants.into_inner())
+		}
+
+		fn count_participants(lottery_id: LotteryId) -> u32 {
+			Participants::<T>::decode_len(lottery_id).unwrap_or(0) as u32
+		}
+
+		fn tickets_held(lottery_id: LotteryId, who: &T::AccountId) -> Option<u32> {
+			Self::get_participants(lottery_id)?
+				.iter()
+				.find(|(participant, _)| participant == who)
+				.map(|(_, tickets)| *tickets)
+		}
+
+		fn round_tickets(lottery_id: LotteryId) -> u32 {
+			Self::total_tickets(&Self::get_participants(lottery_id).unwrap_or_default())
+		}
+
+		// Sets the tickets held by the participant, adding it at the end of the list 
+		// when it's new, and removing it when it holds none. The list is removed 
+		// once empty
+		pub(crate) fn set_tickets(lottery_id: LotteryId, who: &T::AccountId, tickets: u32) -> DispatchResult {
+			let mut participants = Self::get_participants(lottery_id).unwrap_or_default();
+			match participants.iter().position(|(participant, _)| participant == who) {
+				Some(position) if tickets == 0 => {
+					participants.remove(position);
+				},
+				Some(position) => participants[position].1 = tickets,
+				None if tickets == 0 => {},
+				None => participants
+					.try_push((who.clone(), tickets))
+					.map_err(|_| Error::<T>::CanNotAddParticipant)?,
+			}
+			if participants.is_empty() {
+				Participants::<T>::remove(lottery_id);
+			} else {
+				Participants::<T>::insert(lottery_id, participants);
+			}
+			Ok(())
+		}
+
+		// Removes up to the given number of participants from the end of the list, 
+		// and returns them
+		fn take_last_participants(lottery_id: LotteryId, limit: u32) -> Vec<(T::AccountId, u32)> {
+			let mut participants = Self::participants_of(lottery_id);
+			let page = participants.split_off(participants.len().saturating_sub(limit as usize));
+			if participants.is_empty() {
+				Participants::<T>::remove(lottery_id);
+			} else {
+				Participants::<T>::insert(lottery_id, BoundedVec::truncate_from(participants));
+			}
+			page
+		}
+
+		fn clear_participants(lottery_id: LotteryId) {
+			Participants::<T>::remove(lottery_id);
+		}
+	}
+
+	// With indexed participants, each participant of a lottery is stored on its own,
+	// by account and by position, along with the participant and ticket counts, so a
+	// purchase only reads and writes its own entry whatever the number of 
+	// participants. The draw already looks up the winning tickets by number. Removing 
+	// a participant moves the last one into its position, so the order they entered 
+	// in is not kept
+	#[cfg(feature = "indexed-participants")]
+	impl<T: Config> Pallet<T> {
+
+		pub(crate) fn participants_of(lottery_id: LotteryId) -> Vec<(T::AccountId, u32)> {
+			(0..Self::get_participant_count(lottery_id))
+				.filter_map(|index| {
+					let who = Self::get_participant_at(lottery_id, index)?;
+					let (_, tickets) = Self::get_participant_tickets(lottery_id, &who)?;
+					Some((who, tickets))
+				})
+				.collect()
+		}
+
+		fn count_participants(lottery_id: LotteryId) -> u32 {
+			Self::get_participant_count(lottery_id)
+		}
+
+		fn tickets_held(lottery_id: LotteryId, who: &T::AccountId) -> Option<u32> {
+			Self::get_participant_tickets(lottery_id, who).map(|(_, tickets)| tickets)
+		}
+
+		fn round_tickets(lottery_id: LotteryId) -> u32 {
+			Self::get_tickets_held(lottery_id)
+		}
+
+		// Sets the tickets held by the participant, adding it at the last position 
+		// when it's new, and removing it when it holds none, up to the maximum
+		// number of participants
+		pub(crate) fn set_tickets(lottery_id: LotteryId, who: &T::AccountId, tickets: u32) -> DispatchResult {
+			let count = Self::get_participant_count(lottery_id);
+			let (held, count) = match Self::get_participant_tickets(lottery_id, who) {
+				Some((position, held)) if tickets == 0 => {
+					let last = count.saturating_sub(1);
+					if let Some(moved) = ParticipantIndex::<T>::take(lottery_id, last) {
+						if position != last {
+							ParticipantIndex::<T>::insert(lottery_id, position, &moved);
+							ParticipantTickets::<T>::mutate(lottery_id, &moved, |entry| {
+								if let Some((index, _)) = entry {
+									*index = position;
+								}
+							});
+						}
+					}
+					ParticipantTickets::<T>::remove(lottery_id, who);
+					(held, last)
+				},
+				Some((position, held)) => {
+					ParticipantTickets::<T>::insert(lottery_id, who, (position, tickets));
+					(held, count)
+				},
+				None if tickets == 0 => return Ok(()),
+				None => {
+					ensure!(count < T::MaxParticipants::get(), Error::<T>::CanNotAddParticipant);
+					ParticipantIndex::<T>::insert(lottery_id, count, who);
+					ParticipantTickets::<T>::insert(lottery_id, who, (count, tickets));
+					(0, count.saturating_add(1))
+				},
+			};
+			let total = Self::get_tickets_held(lottery_id).saturating_sub(held).saturating_add(tickets);
+			Self::put_counts(lottery_id, count, total);
+			Ok(())
+		}
+
+		// Removes up to the given number of participants from the last positions,
+		// and returns them
+		fn take_last_participants(lottery_id: LotteryId, limit: u32) -> Vec<(T::AccountId, u32)> {
+			let count = Self::get_participant_count(lottery_id);
+			let first = count.saturating_sub(limit);
+			let page: Vec<_> = (first..count)
+				.filter_map(|index| {
+					let who = ParticipantIndex::<T>::take(lottery_id, index)?;
+					let (_, tickets) = ParticipantTickets::<T>::take(lottery_id, &who)?;
+					Some((who, tickets))
+				})
+				.collect();
+			let total = Self::get_tickets_held(lottery_id).saturating_sub(Self::total_tickets(&page));
+			Self::put_counts(lottery_id, first, total);
+			page
+		}
+
+		fn clear_participants(lottery_id: LotteryId) {
+			let count = ParticipantCount::<T>::take(lottery_id);
+			TicketsHeld::<T>::remove(lottery_id);
+			let _ = ParticipantIndex::<T>::clear_prefix(lottery_id, count, None);
+			let _ = ParticipantTickets::<T>::clear_prefix(lottery_id, count, None);
+		}
+
+		// Stores the participant and ticket counts, which are removed once there 
+		// are no participants left
+		fn put_counts(lottery_id: LotteryId, count: u32, tickets: u32) {
+			if count == 0 {
+				ParticipantCount::<T>::remove(lottery_id);
+				TicketsHeld::<T>::remove(lottery_id);
+			} else {
+				ParticipantCount::<T>::insert(lottery_id, count);
+				TicketsHeld::<T>::insert(lottery_id, tickets);
+			}
+		}
+	}
+
+	// When paying in the native currency, the ticket funds are reserved on each buyer's
+	// own account, and repatriated to the lottery's account when the prize is awarded
+	#[cfg(not(feature = "fungibles"))]
+	impl<T: Config> Pallet<T> {
+
+		fn can_afford(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			T::Currency::free_balance(who) >= amount
+		}
+
+		// The funds paid for someone else are given to the beneficiary, and 
+		// reserved on the beneficiary's account
+		fn hold_ticket_funds(
+			_lottery_id: LotteryId,
+			payer: &T::AccountId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			if payer != who {
+				T::Currency::transfer(payer, who, amount, ExistenceRequirement::KeepAlive)?;
+			}
+			T::Currency::reserve_named(&Self::reserve_id(), who, amount)
+		}
+
+		// Returns the amount that could not be refunded
+		fn release_ticket_funds(
+			_lottery_id: LotteryId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			Ok(T::Currency::unreserve_named(&Self::reserve_id(), who, amount))
+		}
+
+		// Returns the amount collected, without the funds slashed in the meantime
+		fn collect_ticket_funds(
+			lottery_id: LotteryId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			T::Currency::repatriate_reserved_named(
+				&Self::reserve_id(),
+				who, 
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				BalanceStatus::Free).map(|unmoved| amount.saturating_sub(unmoved))
+		}
+
+		fn deposit_to_pot(
+			lottery_id: LotteryId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::Currency::transfer(
+				who, 
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				ExistenceRequirement::KeepAlive)
+		}
+
+		fn pot_balance(lottery_id: LotteryId) -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::get_pallet_account(lottery_id))
+		}
+
+		// Whether the amount can be transferred to the account, which it can't when
+		// the account doesn't exist yet and the amount is below the existential deposit
+		fn can_receive(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			amount >= T::Currency::minimum_balance() || !T::Currency::total_balance(who).is_zero()
+		}
+
+		fn uncollected_ticket_funds(lottery_id: LotteryId) -> BalanceOf<T> {
+			ReservedFunds::<T>::iter_prefix_values(lottery_id)
+				.fold(Zero::zero(), |total: BalanceOf<T>, held| total.saturating_add(held))
+		}
+
+		// The ticket funds are held on the buyers' accounts
+		fn escrowed_ticket_funds(_lottery_id: LotteryId) -> BalanceOf<T> {
+			Zero::zero()
+		}
+
+		// The ticket funds are always collected into the lottery's account
+		fn ticket_funds_in_pot() -> bool {
+			true
+		}
+
+		// The funds prepaid by the subscribers are reserved on their accounts
+		fn prepaid_funds(_lottery_id: LotteryId) -> BalanceOf<T> {
+			Zero::zero()
+		}
+
+		// The lottery's account may be reaped once it pays out everything it holds, but 
+		// must be kept alive while it holds funds carried over or owed to other winners, 
+		// as reaping it would drop the remaining balance below the existential deposit.
+		// A payout that would reap it then fails, and the prize can be claimed later
+		fn pay_out(lottery_id: LotteryId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			let existence = Self::payout_existence(lottery_id);
+			T::Currency::transfer(&Self::get_pallet_account(lottery_id), to, amount, existence)
+		}
+
+		// Withdraws the amount from the lottery's account, dropping the imbalance 
+		// so the total issuance goes down by the same amount
+		fn burn(lottery_id: LotteryId, amount: BalanceOf<T>) -> DispatchResult {
+			let existence = Self::payout_existence(lottery_id);
+			T::Currency::withdraw(
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				WithdrawReasons::TRANSFER, 
+				existence).map(|_| ())
+		}
+
+		fn payout_existence(lottery_id: LotteryId) -> ExistenceRequirement {
+			if Self::holds_remaining_funds(lottery_id) {
+				ExistenceRequirement::KeepAlive
+			} else {
+				ExistenceRequirement::AllowDeath
+			}
+		}
+	}
+
+	// When paying in an asset, which can't be reserved, the ticket funds are 
+	// transferred to the lottery's account as soon as the tickets are bought.
+	// The prize pool is kept in the prize asset, which holds the ticket funds 
+	// too when the tickets are paid in the same asset
+	#[cfg(feature = "fungibles")]
+	impl<T: Config> Pallet<T> {
+
+		// Whether the ticket funds make up the prize, being in the prize asset
+		fn ticket_funds_in_pot() -> bool {
+			T::TicketAsset::get() == T::PrizeAsset::get()
+		}
+
+		// Transfers ticket funds out of the lottery's account, which is kept alive
+		// while it holds the funds of other participants
+		fn transfer_ticket_funds(
+			lottery_id: LotteryId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let preservation = if ReservedFunds::<T>::iter_key_prefix(lottery_id).next().is_some() {
+				Preservation::Preserve
+			} else {
+				Preservation::Expendable
+			};
+			<T::Assets as fungibles::Mutate<_>>::transfer(
+				T::TicketAsset::get(), 
+				&Self::get_pallet_account(lottery_id), 
+				to, 
+				amount, 
+				preservation).map(|_| ())
+		}
+
+		fn can_afford(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			<T::Assets as fungibles::Inspect<_>>::reducible_balance(
+				T::TicketAsset::get(), 
+				who, 
+				Preservation::Preserve, 
+				Fortitude::Polite) >= amount
+		}
+
+		fn hold_ticket_funds(
+			lottery_id: LotteryId,
+			payer: &T::AccountId,
+			_who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			<T::Assets as fungibles::Mutate<_>>::transfer(
+				T::TicketAsset::get(), 
+				payer, 
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				Preservation::Preserve).map(|_| ())
+		}
+
+		// Returns the amount that could not be refunded
+		fn release_ticket_funds(
+			lottery_id: LotteryId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let refunded = if Self::ticket_funds_in_pot() {
+				Self::pay_out(lottery_id, who, amount)
+			} else {
+				Self::transfer_ticket_funds(lottery_id, who, amount)
+			};
+			refunded.map(|_| Zero::zero())
+		}
+
+		// The funds are already in the lottery's account, and make up the prize
+		// unless it's paid in another asset, in which case they go to the fee
+		// destination
+		// Returns the amount collected, which is all of it
+		fn collect_ticket_funds(
+			lottery_id: LotteryId,
+			_who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			if !Self::ticket_funds_in_pot() && !amount.is_zero() {
+				Self::transfer_ticket_funds(lottery_id, &T::FeeDestination::get(), amount)?;
+			}
+			Ok(amount)
+		}
+
+		fn deposit_to_pot(
+			lottery_id: LotteryId,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			<T::Assets as fungibles::Mutate<_>>::transfer(
+				T::PrizeAsset::get(), 
+				who, 
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				Preservation::Preserve).map(|_| ())
+		}
+
+		fn pot_balance(lottery_id: LotteryId) -> BalanceOf<T> {
+			<T::Assets as fungibles::Inspect<_>>::balance(
+				T::PrizeAsset::get(), 
+				&Self::get_pallet_account(lottery_id))
+		}
+
+		// Whether the amount can be transferred to the account, which it can't when
+		// the account holds none of the prize asset and the amount is below the 
+		// asset's minimum balance
+		fn can_receive(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			amount >= <T::Assets as fungibles::Inspect<_>>::minimum_balance(T::PrizeAsset::get()) || 
+				!<T::Assets as fungibles::Inspect<_>>::balance(T::PrizeAsset::get(), who).is_zero()
+		}
+
+		// The funds are already in the lottery's account
+		fn uncollected_ticket_funds(_lottery_id: LotteryId) -> BalanceOf<T> {
+			Zero::zero()
+		}
+
+		// The ticket funds held in the lottery's account along with the prize, 
+		// including the funds prepaid by the subscribers, when the tickets are 
+		// paid in the prize asset
+		fn escrowed_ticket_funds(lottery_id: LotteryId) -> BalanceOf<T> {
+			if !Self::ticket_funds_in_pot() {
+				return Zero::zero();
+			}
+			ReservedFunds::<T>::iter_prefix_values(lottery_id)
+				.fold(Self::prepaid_funds(lottery_id), |total: BalanceOf<T>, held| total.saturating_add(held))
+		}
+
+		// The funds prepaid by the subscribers held in the lottery's account, when
+		// the tickets are paid in the prize asset
+		fn prepaid_funds(lottery_id: LotteryId) -> BalanceOf<T> {
+			if !Self::ticket_funds_in_pot() {
+				return Zero::zero();
+			}
+			Subscriptions::<T>::iter_prefix_values(lottery_id)
+				.fold(Zero::zero(), |total: BalanceOf<T>, subscription| total.saturating_add(subscription.funds))
+		}
+
+		// The lottery's account is kept alive while it holds funds carried over or 
+		// owed to other winners, as on the native currency
+		fn pay_out(lottery_id: LotteryId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			let preservation = if Self::holds_remaining_funds(lottery_id) {
+				Preservation::Preserve
+			} else {
+				Preservation::Expendable
+			};
+			<T::Assets as fungibles::Mutate<_>>::transfer(
+				T::PrizeAsset::get(), 
+				&Self::get_pallet_account(lottery_id), 
+				to, 
+				amount, 
+				preservation).map(|_| ())
+		}
+
+		// Burns the amount from the lottery's account, lowering the asset's supply
+		fn burn(lottery_id: LotteryId, amount: BalanceOf<T>) -> DispatchResult {
+			<T::Assets as fungibles::Mutate<_>>::burn_from(
+				T::PrizeAsset::get(), 
+				&Self::get_pallet_account(lottery_id), 
+				amount, 
+				Precision::Exact, 
+				Fortitude::Polite).map(|_| ())
+		}
 	}
 }